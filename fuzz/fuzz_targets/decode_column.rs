@@ -0,0 +1,32 @@
+// [[file:../../wills-columnar-format.org::#FormatSpecificationFooterValidationFuzzing-b8t4x2m25tj0][Fuzzing:6]]
+#![no_main]
+
+use std::io::Cursor;
+
+use columnar_format::{decode_column, verify_file, ColumnItem, FromColumnValue, List};
+use columnar_format_fuzz::{build_file_with_checksums, split_input};
+use libfuzzer_sys::fuzz_target;
+
+fn decode_all<T>(file: &[u8])
+where
+    T: 'static + ColumnItem + Clone,
+    T::Value: FromColumnValue,
+{
+    if let Ok(reader) = decode_column::<T>(Cursor::new(file)) {
+        reader.for_each(drop);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let (pages, footer) = split_input(data);
+    let Some(file) = build_file_with_checksums(pages, footer) else {
+        return;
+    };
+    let _ = verify_file(Cursor::new(&file));
+    decode_all::<i64>(&file);
+    decode_all::<Option<String>>(&file);
+    decode_all::<List<f64>>(&file);
+    decode_all::<bool>(&file);
+    decode_all::<Vec<u8>>(&file);
+});
+// Fuzzing:6 ends here
@@ -0,0 +1,14 @@
+// [[file:../../wills-columnar-format.org::#FormatSpecificationFooterValidationFuzzing-b8t4x2m25tj0][Fuzzing:5]]
+#![no_main]
+
+use std::io::Cursor;
+
+use columnar_format::decode_footer;
+use columnar_format_fuzz::{build_file, split_input};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (pages, footer) = split_input(data);
+    let _ = decode_footer(Cursor::new(build_file(pages, footer)));
+});
+// Fuzzing:5 ends here
@@ -0,0 +1,44 @@
+// [[file:../../wills-columnar-format.org::#FormatSpecificationFooterValidationFuzzing-b8t4x2m25tj0][Fuzzing:3]]
+use std::io::Cursor;
+
+use columnar_format::{decode_footer, MAGIC_BYTES};
+
+pub fn split_input(data: &[u8]) -> (&[u8], &[u8]) {
+    match data {
+        [a, b, rest @ ..] => {
+            let pages_size = u16::from_le_bytes([*a, *b]) as usize;
+            rest.split_at(pages_size.min(rest.len()))
+        }
+        _ => (&[], data),
+    }
+}
+
+pub fn build_file(pages: &[u8], footer: &[u8]) -> Vec<u8> {
+    let mut file = MAGIC_BYTES.to_vec();
+    file.extend_from_slice(pages);
+    file.extend_from_slice(footer);
+    file.extend_from_slice(&crc32c::crc32c(footer).to_le_bytes());
+    file.extend_from_slice(&(footer.len() as u64).to_le_bytes());
+    file.extend_from_slice(&MAGIC_BYTES);
+    file
+}
+// Fuzzing:3 ends here
+
+// [[file:../../wills-columnar-format.org::#FormatSpecificationFooterValidationFuzzing-b8t4x2m25tj0][Fuzzing:4]]
+pub fn build_file_with_checksums(pages: &[u8], footer: &[u8]) -> Option<Vec<u8>> {
+    let file = build_file(pages, footer);
+    let mut footer = decode_footer(Cursor::new(&file)).ok()?;
+    for column in footer.columns.iter_mut() {
+        for page in column.pages.iter_mut() {
+            let start = page.file_offset as usize;
+            page.checksum = crc32c::crc32c(&file[start..start + page.compressed_size]);
+        }
+        if let Some(dictionary) = column.dictionary.as_mut() {
+            let start = dictionary.file_offset as usize;
+            dictionary.checksum = crc32c::crc32c(&file[start..start + dictionary.size]);
+        }
+    }
+    let footer = bincode::encode_to_vec(&footer, bincode::config::standard()).ok()?;
+    Some(build_file(pages, &footer))
+}
+// Fuzzing:4 ends here
@@ -0,0 +1,6 @@
+// [[file:../wills-columnar-format.org::#DataEncodingBinaryEncoding-f3z8r6d16tj0][Binary Encoding:2]]
+pub fn fixed_size(values: &[&[u8]]) -> Option<usize> {
+    let size = values.first()?.len();
+    values.iter().all(|v| v.len() == size).then_some(size)
+}
+// Binary Encoding:2 ends here
@@ -0,0 +1,39 @@
+// [[file:../wills-columnar-format.org::#DataEncodingBitPacking-r5d9k3c08tj0][Bit Packing:2]]
+pub fn bit_width(max_offset: u128) -> u8 {
+    (u128::BITS - max_offset.leading_zeros()) as u8
+}
+
+pub fn packed_size(values_count: usize, bit_width: u8) -> usize {
+    (values_count * bit_width as usize).div_ceil(8)
+}
+
+pub fn pack(offsets: &[u128], bit_width: u8) -> Vec<u8> {
+    let bit_width = bit_width as usize;
+    let mut packed = vec![0u8; packed_size(offsets.len(), bit_width as u8)];
+    for (idx, offset) in offsets.iter().enumerate() {
+        for bit in 0..bit_width {
+            if (offset >> bit) & 1 == 1 {
+                let position = idx * bit_width + bit;
+                packed[position / 8] |= 1 << (position % 8);
+            }
+        }
+    }
+    packed
+}
+
+pub fn unpack(packed: &[u8], bit_width: u8, values_count: usize) -> Vec<u128> {
+    let bit_width = bit_width as usize;
+    (0..values_count)
+        .map(|idx| {
+            let mut offset = 0u128;
+            for bit in 0..bit_width {
+                let position = idx * bit_width + bit;
+                if (packed[position / 8] >> (position % 8)) & 1 == 1 {
+                    offset |= 1 << bit;
+                }
+            }
+            offset
+        })
+        .collect()
+}
+// Bit Packing:2 ends here
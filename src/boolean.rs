@@ -0,0 +1,76 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:20]]
+use std::io::Read;
+
+use crate::{bitpack, rle, Error, Result, BINCODE_DATA_CONFIG};
+// Dependencies:20 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBooleanEncoding-a7x4m2g15tj0][Boolean Encoding:1]]
+const MIN_RUN_LENGTH: usize = 16;
+
+pub fn encode(values: &[bool]) -> Result<Vec<u8>> {
+    let mut encoded = Vec::new();
+    let mut literal_start = 0;
+    let mut run_start = 0;
+    while run_start < values.len() {
+        let value = values[run_start];
+        let run_length = values[run_start..]
+            .iter()
+            .take_while(|v| **v == value)
+            .count();
+        if run_length >= MIN_RUN_LENGTH {
+            encode_literal(&values[literal_start..run_start], &mut encoded)?;
+            let header = (run_length as u64) << 2 | (value as u64) << 1 | 1;
+            bincode::encode_into_std_write(header, &mut encoded, BINCODE_DATA_CONFIG)?;
+            literal_start = run_start + run_length;
+        }
+        run_start += run_length;
+    }
+    encode_literal(&values[literal_start..], &mut encoded)?;
+    Ok(encoded)
+}
+
+fn encode_literal(values: &[bool], encoded: &mut Vec<u8>) -> Result<()> {
+    if values.is_empty() {
+        return Ok(());
+    }
+    let header = (values.len() as u64) << 1;
+    bincode::encode_into_std_write(header, &mut *encoded, BINCODE_DATA_CONFIG)?;
+    let bits: Vec<u128> = values.iter().map(|v| *v as u128).collect();
+    encoded.extend(bitpack::pack(&bits, 1));
+    Ok(())
+}
+// Boolean Encoding:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBooleanEncoding-a7x4m2g15tj0][Boolean Encoding:3]]
+pub fn decode(r: &mut impl Read, values_count: usize) -> Result<Vec<rle::Values<bool>>> {
+    let mut values = Vec::new();
+    let mut decoded_count = 0;
+    while decoded_count < values_count {
+        let header: u64 = bincode::decode_from_std_read(r, BINCODE_DATA_CONFIG)?;
+        let count = if header & 1 == 1 {
+            header >> 2
+        } else {
+            header >> 1
+        } as usize;
+        if count > values_count - decoded_count {
+            return Err(Error::PageCountMismatch {
+                expected: values_count,
+                actual: decoded_count + count,
+            });
+        }
+        if header & 1 == 1 {
+            values.push(rle::Values {
+                value: header & 2 == 2,
+                run_length: count as u64,
+            });
+        } else {
+            let mut packed = vec![0u8; bitpack::packed_size(count, 1)];
+            r.read_exact(&mut packed)?;
+            let bits = bitpack::unpack(&packed, 1, count);
+            values.extend(bits.into_iter().map(|bit| rle::Values::single(bit == 1)));
+        }
+        decoded_count += count;
+    }
+    Ok(values)
+}
+// Boolean Encoding:3 ends here
@@ -0,0 +1,73 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:25]]
+use std::io::{Read, Write};
+
+use crate::{Compression, Error, Result};
+// Dependencies:25 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCompression-t6c3v9h10tj0][Compression:1]]
+pub fn compress(compression: Compression, data: &[u8]) -> Result<Vec<u8>> {
+    let compressed = match compression {
+        Compression::None => data.to_vec(),
+        Compression::Zstd => zstd::bulk::compress(data, zstd::DEFAULT_COMPRESSION_LEVEL)?,
+        Compression::Lz4 => lz4_flex::compress(data),
+        Compression::Snappy => snap::raw::Encoder::new()
+            .compress_vec(data)
+            .map_err(|err| codec_error(compression, &err))?,
+        Compression::Deflate => {
+            let mut encoder =
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+    };
+    Ok(compressed)
+}
+
+pub fn decompress(
+    compression: Compression,
+    data: &[u8],
+    uncompressed_size: usize,
+) -> Result<Vec<u8>> {
+    let decompressed = match compression {
+        Compression::None => data.to_vec(),
+        Compression::Zstd => zstd::bulk::decompress(data, uncompressed_size)
+            .map_err(|err| codec_error(compression, &err))?,
+        Compression::Lz4 => lz4_flex::decompress(data, uncompressed_size)
+            .map_err(|err| codec_error(compression, &err))?,
+        Compression::Snappy => {
+            let mut decompressed = vec![0u8; uncompressed_size];
+            let len = snap::raw::Decoder::new()
+                .decompress(data, &mut decompressed)
+                .map_err(|err| codec_error(compression, &err))?;
+            decompressed.truncate(len);
+            decompressed
+        }
+        Compression::Deflate => {
+            let mut decompressed = Vec::with_capacity(uncompressed_size);
+            flate2::read::DeflateDecoder::new(data)
+                .take(uncompressed_size as u64 + 1)
+                .read_to_end(&mut decompressed)
+                .map_err(|err| codec_error(compression, &err))?;
+            decompressed
+        }
+    };
+    if decompressed.len() != uncompressed_size {
+        return Err(codec_error(
+            compression,
+            &format!(
+                "decompressed to {} bytes but expected {} bytes",
+                decompressed.len(),
+                uncompressed_size
+            ),
+        ));
+    }
+    Ok(decompressed)
+}
+
+fn codec_error(compression: Compression, err: &dyn std::fmt::Display) -> Error {
+    Error::Codec {
+        compression,
+        reason: err.to_string(),
+    }
+}
+// Compression:1 ends here
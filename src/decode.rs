@@ -1,69 +1,1144 @@
-// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:5]]
-use std::io::{Read, Seek};
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:6]]
+use std::{
+    collections::{HashSet, VecDeque},
+    io::{Read, Seek, SeekFrom},
+    ops::Range,
+};
 
-use crate::{rle, DataType, Footer, Result, BINCODE_DATA_CONFIG};
+use bincode::Decode;
+use itertools::Itertools;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-struct UnsupportedDataType {
-    expected: DataType,
-    actual: &'static str,
+use crate::{
+    bitpack, boolean, compression, delta::DeltaDecoder, float, rle, xor, ColumnInfo, ColumnItem,
+    ColumnValue, Compression, DataType, DecodeOptions, DictionaryInfo, Error, Footer,
+    FromColumnValue, PageEncoding, PageInfo, PhysicalValue, Result, BINCODE_DATA_CONFIG,
+    MAGIC_BYTES, MAX_DECODE_ALLOCATION, MAX_PAGE_VALUES,
+};
+// Dependencies:6 ends here
+
+// [[file:../wills-columnar-format.org::#APIRowSkipping-d3m7q1k11tj0][Row Skipping:1]]
+// Decodes the bytes of a page. The dictionary of the column, if any, is owned by
+// the decoder.
+type PageDecoder<T> = Box<dyn Fn(&mut &[u8], &PageInfo) -> Result<Vec<rle::Values<T>>>>;
+
+pub struct ColumnReader<R, T> {
+    r: R,
+    data_start: u64,
+    pages: Vec<PageInfo>,
+    decode_page: PageDecoder<T>,
+    // The index of the next page to decode.
+    next_page: usize,
+    // Values that were decoded but not yet returned.
+    values: VecDeque<rle::Values<T>>,
+    // If r must be moved to the start of the next page before reading.
+    needs_seek: bool,
 }
 
-impl std::error::Error for UnsupportedDataType {}
+impl<R: Read + Seek, T: 'static + Clone> ColumnReader<R, T> {
+    fn new(
+        r: R,
+        data_start: u64,
+        pages: Vec<PageInfo>,
+        decode_page: PageDecoder<T>,
+    ) -> ColumnReader<R, T> {
+        ColumnReader {
+            r,
+            data_start,
+            pages,
+            decode_page,
+            next_page: 0,
+            values: VecDeque::new(),
+            needs_seek: true,
+        }
+    }
 
-impl std::fmt::Display for UnsupportedDataType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Expected format of type {:?} but got {}",
-            self.expected, self.actual
-        )
+    pub fn seek_to_row(&mut self, row: usize) -> Result<()> {
+        self.next_page = 0;
+        self.values.clear();
+        self.needs_seek = true;
+        self.skip_rows(row)
+    }
+
+    pub fn skip_rows(&mut self, rows: usize) -> Result<()> {
+        let mut rows = rows;
+        while rows > 0 {
+            if self.values.is_empty() {
+                match self.pages.get(self.next_page) {
+                    None => return Ok(()),
+                    Some(page) if page.values_count <= rows => {
+                        rows -= page.values_count;
+                        self.next_page += 1;
+                        self.needs_seek = true;
+                    }
+                    Some(_) => self.decode_next_page()?,
+                }
+                continue;
+            }
+            let front = self.values.front_mut().unwrap();
+            if front.run_length as usize <= rows {
+                rows -= front.run_length as usize;
+                self.values.pop_front();
+            } else {
+                front.run_length -= rows as u64;
+                rows = 0;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rows_count(&self) -> usize {
+        self.pages.iter().map(|p| p.values_count).sum()
+    }
+
+    // Returns the value at the current row without advancing the reader.
+    fn peek(&mut self) -> Result<Option<&T>> {
+        while self.values.is_empty() && self.next_page < self.pages.len() {
+            self.decode_next_page()?;
+        }
+        Ok(self.values.front().map(|v| &v.value))
+    }
+
+    fn decode_next_page(&mut self) -> Result<()> {
+        let page = self.pages[self.next_page];
+        self.next_page += 1;
+        if page.values_count == 0 {
+            return Ok(());
+        }
+        if self.needs_seek {
+            self.r
+                .seek(SeekFrom::Start(self.data_start + page.file_offset as u64))?;
+        }
+        // If reading fails partway, the position of r is unknown.
+        self.needs_seek = true;
+        let page_bytes = read_page(&mut self.r, &page)?;
+        self.needs_seek = false;
+        let values = (self.decode_page)(&mut page_bytes.as_slice(), &page)?;
+        self.values.extend(values);
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek, T: 'static + Clone> Iterator for ColumnReader<R, T> {
+    type Item = Result<rle::Values<T>>;
+
+    fn next(&mut self) -> Option<Result<rle::Values<T>>> {
+        if let Err(err) = self.peek() {
+            return Some(Err(err));
+        }
+        self.values.pop_front().map(Ok)
+    }
+}
+// Row Skipping:1 ends here
+
+// [[file:../wills-columnar-format.org::#APIRowSkipping-d3m7q1k11tj0][Row Skipping:2]]
+impl<R: Read + Seek, T: 'static + Clone> ColumnReader<R, T> {
+    pub fn get(&mut self, row: usize) -> Result<T> {
+        self.seek_to_row(row)?;
+        match self.peek()? {
+            Some(v) => Ok(v.clone()),
+            None => Err(Error::RowOutOfRange {
+                row,
+                rows_count: self.rows_count(),
+            }),
+        }
+    }
+
+    pub fn take_rows(&mut self, rows: &[usize]) -> Result<Vec<T>> {
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        order.sort_by_key(|idx| rows[*idx]);
+        let mut values = vec![None; rows.len()];
+        let mut current_row = None;
+        for idx in order {
+            let row = rows[idx];
+            match current_row {
+                Some(current_row) => self.skip_rows(row - current_row)?,
+                None => self.seek_to_row(row)?,
+            }
+            current_row = Some(row);
+            match self.peek()? {
+                Some(v) => values[idx] = Some(v.clone()),
+                None => {
+                    return Err(Error::RowOutOfRange {
+                        row,
+                        rows_count: self.rows_count(),
+                    })
+                }
+            }
+        }
+        Ok(values.into_iter().flatten().collect())
+    }
+}
+// Row Skipping:2 ends here
+
+// [[file:../wills-columnar-format.org::#APIRowSkipping-d3m7q1k11tj0][Row Skipping:3]]
+pub fn decode_range_impl<R: Read + Seek, T: 'static + Clone>(
+    reader: ColumnReader<R, T>,
+    range: Range<usize>,
+) -> Result<impl Iterator<Item = Result<rle::Values<T>>>> {
+    let rows_count = reader.rows_count();
+    if range.end > rows_count {
+        return Err(Error::RowOutOfRange {
+            row: range.end - 1,
+            rows_count,
+        });
+    }
+    if range.start > range.end {
+        return Err(Error::RowOutOfRange {
+            row: range.start,
+            rows_count,
+        });
+    }
+    let mut reader = reader;
+    reader.seek_to_row(range.start)?;
+    let mut remaining_rows = range.len();
+    let iter = std::iter::from_fn(move || {
+        if remaining_rows == 0 {
+            return None;
+        }
+        let mut values = match reader.next()? {
+            Ok(v) => v,
+            Err(err) => {
+                remaining_rows = 0;
+                return Some(Err(err));
+            }
+        };
+        if values.run_length as usize > remaining_rows {
+            values.run_length = remaining_rows as u64;
+        }
+        remaining_rows -= values.run_length as usize;
+        Some(Ok(values))
+    });
+    Ok(iter)
+}
+// Row Skipping:3 ends here
+
+// [[file:../wills-columnar-format.org::#APISchemaEvolution-r8w3k5j16tj0][Schema Evolution:2]]
+fn widened_values_decoder<T: PhysicalValue>(
+    r: &mut (impl Read + Seek),
+    data_start: u64,
+    column: &ColumnInfo,
+    options: DecodeOptions,
+    width: u8,
+) -> Result<PageDecoder<T>> {
+    let can_widen = options.allow_widening
+        && T::INTEGER_WIDTH > Some(width)
+        && T::DATA_TYPE.is_signed_integer() == column.data_type.is_signed_integer();
+    if !can_widen {
+        return Err(Error::IntegerWidthMismatch {
+            width,
+            actual: std::any::type_name::<T>(),
+        });
+    }
+    macro_rules! widen_from {
+        ($($t:ty),*) => {
+            $(
+                if <$t>::DATA_TYPE == column.data_type && <$t>::INTEGER_WIDTH == Some(width) {
+                    let decode_values = values_decoder::<$t>(r, data_start, column, options)?;
+                    return Ok(Box::new(move |r, page| {
+                        decode_values(r, page)?.into_iter().map(widen).collect()
+                    }));
+                }
+            )*
+        };
+    }
+    widen_from!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+    Err(Error::CorruptFooter {
+        reason: format!(
+            "{:?} column has an invalid integer width of {}",
+            column.data_type, width
+        ),
+    })
+}
+
+fn widen<V: PhysicalValue, T: PhysicalValue>(values: rle::Values<V>) -> Result<rle::Values<T>> {
+    let value = values.value.to_i128().expect("integer data type");
+    match T::from_i128(value) {
+        Some(v) => Ok(rle::Values {
+            value: v,
+            run_length: values.run_length,
+        }),
+        None => Err(Error::IntegerOutOfRange {
+            value,
+            actual: std::any::type_name::<T>(),
+        }),
     }
 }
-// Dependencies:5 ends here
+// Schema Evolution:2 ends here
 
 // [[file:../wills-columnar-format.org::#FormatSpecificationFormatOverview-j3k696o03tj0][Format Overview:3]]
+fn read_magic_bytes(r: &mut impl Read) -> Result<()> {
+    let mut found = [0u8; 4];
+    r.read_exact(&mut found)?;
+    if found != MAGIC_BYTES {
+        return Err(Error::InvalidMagicBytes { found });
+    }
+    Ok(())
+}
+
+fn check_magic_bytes(r: &mut (impl Read + Seek), data_start: u64, file_size: u64) -> Result<()> {
+    let magic_size = MAGIC_BYTES.len() as u64;
+    if file_size < 2 * magic_size {
+        return Err(Error::CorruptFooter {
+            reason: format!("file of {} bytes is too small for a footer", file_size),
+        });
+    }
+    r.seek(SeekFrom::Start(data_start))?;
+    read_magic_bytes(r)?;
+    r.seek(SeekFrom::Start(data_start + file_size - magic_size))?;
+    read_magic_bytes(r)
+}
+
+fn decode_footer_bytes(footer_bytes: &[u8]) -> Result<Footer> {
+    let (version, _): (u16, usize) = bincode::decode_from_slice(footer_bytes, BINCODE_DATA_CONFIG)?;
+    match version {
+        1 => {
+            let (footer, _) = bincode::decode_from_slice(footer_bytes, BINCODE_DATA_CONFIG)?;
+            Ok(footer)
+        }
+        _ => Err(Error::UnsupportedVersion { version }),
+    }
+}
+// Format Overview:3 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFormatOverview-j3k696o03tj0][Format Overview:4]]
+// The footer of a version 0 file.
+#[derive(Decode)]
+struct FooterV0 {
+    data_type: DataType,
+    use_rle: bool,
+    pages: Vec<PageInfoV0>,
+}
+
+#[derive(Decode)]
+struct PageInfoV0 {
+    file_offset: i64,
+    values_count: usize,
+    encoded_values_count: usize,
+}
+
+fn decode_footer_v0(r: &mut (impl Read + Seek), data_start: u64, file_size: u64) -> Result<Footer> {
+    let trailer_size = 8;
+    if file_size < trailer_size {
+        return Err(Error::CorruptFooter {
+            reason: format!("file of {} bytes is too small for a footer", file_size),
+        });
+    }
+    r.seek(SeekFrom::Start(data_start + file_size - trailer_size))?;
+    let mut footer_length_bytes = [0u8; 8];
+    r.read_exact(&mut footer_length_bytes)?;
+    let footer_length = u64::from_le_bytes(footer_length_bytes);
+    if footer_length > file_size - trailer_size {
+        return Err(Error::CorruptFooter {
+            reason: format!(
+                "footer of {} bytes does not fit in file of {} bytes",
+                footer_length, file_size
+            ),
+        });
+    }
+    let footer_start = file_size - trailer_size - footer_length;
+    r.seek(SeekFrom::Start(data_start + footer_start))?;
+    let mut footer_bytes = vec![0u8; footer_length as usize];
+    r.read_exact(&mut footer_bytes)?;
+    let (footer_v0, _): (FooterV0, usize) =
+        bincode::decode_from_slice(&footer_bytes, BINCODE_DATA_CONFIG).map_err(|err| {
+            Error::CorruptFooter {
+                reason: err.to_string(),
+            }
+        })?;
+    let page_ends = footer_v0
+        .pages
+        .iter()
+        .skip(1)
+        .map(|p| p.file_offset)
+        .chain([footer_start as i64]);
+    let pages = footer_v0
+        .pages
+        .iter()
+        .zip(page_ends)
+        .map(|(page, end)| {
+            let size = end
+                .checked_sub(page.file_offset)
+                .and_then(|size| usize::try_from(size).ok())
+                .unwrap_or(usize::MAX);
+            PageInfo {
+                file_offset: page.file_offset,
+                values_count: page.values_count,
+                null_count: 0,
+                encoded_values_count: page.encoded_values_count,
+                encoding: PageEncoding::Plain,
+                use_rle: footer_v0.use_rle,
+                compression: Compression::None,
+                uncompressed_size: size,
+                compressed_size: size,
+                checksum: 0,
+            }
+        })
+        .collect();
+    let mut footer = Footer {
+        version: 0,
+        columns: vec![ColumnInfo {
+            name: String::new(),
+            data_type: footer_v0.data_type,
+            integer_width: None,
+            logical_type: None,
+            nullable: false,
+            repeated: false,
+            null_count: 0,
+            dictionary: None,
+            categories: Vec::new(),
+            pages,
+        }],
+    };
+    validate_footer(&footer, footer_start)?;
+    for page in footer.columns[0].pages.iter_mut() {
+        r.seek(SeekFrom::Start(data_start + page.file_offset as u64))?;
+        let mut page_bytes = vec![0u8; page.compressed_size];
+        r.read_exact(&mut page_bytes)?;
+        page.checksum = crc32c::crc32c(&page_bytes);
+    }
+    Ok(footer)
+}
+// Format Overview:4 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFormatOverview-j3k696o03tj0][Format Overview:6]]
 pub fn decode_footer_impl(r: &mut (impl Read + Seek)) -> Result<Footer> {
     let mut r = r;
-    r.seek(std::io::SeekFrom::End(-8))?;
-    let footer_length_bytes = bincode::decode_from_std_read(&mut r, BINCODE_DATA_CONFIG)?;
+    let data_start = r.stream_position()?;
+    let file_size = r.seek(SeekFrom::End(0))? - data_start;
+    if let Err(err) = check_magic_bytes(&mut r, data_start, file_size) {
+        return decode_footer_v0(&mut r, data_start, file_size).map_err(|_| err);
+    }
+    let trailer_size = 4 + 8 + MAGIC_BYTES.len() as u64;
+    if file_size < MAGIC_BYTES.len() as u64 + trailer_size {
+        return Err(Error::CorruptFooter {
+            reason: format!("file of {} bytes is too small for a footer", file_size),
+        });
+    }
+    r.seek(SeekFrom::End(-(trailer_size as i64)))?;
+    let mut checksum_bytes = [0u8; 4];
+    r.read_exact(&mut checksum_bytes)?;
+    let mut footer_length_bytes = [0u8; 8];
+    r.read_exact(&mut footer_length_bytes)?;
     let footer_length = u64::from_le_bytes(footer_length_bytes);
-    r.seek(std::io::SeekFrom::End(-8 - footer_length as i64))?;
-    let footer: Footer = bincode::decode_from_std_read(&mut r, BINCODE_DATA_CONFIG)?;
+    if footer_length > file_size - MAGIC_BYTES.len() as u64 - trailer_size {
+        return Err(Error::CorruptFooter {
+            reason: format!(
+                "footer of {} bytes does not fit in file of {} bytes",
+                footer_length, file_size
+            ),
+        });
+    }
+    let footer_start = file_size - trailer_size - footer_length;
+    r.seek(SeekFrom::Start(data_start + footer_start))?;
+    let mut footer_bytes = vec![0u8; footer_length as usize];
+    r.read_exact(&mut footer_bytes)?;
+    verify_checksum(
+        &footer_bytes,
+        u32::from_le_bytes(checksum_bytes),
+        footer_start as i64,
+    )?;
+    let footer = decode_footer_bytes(&footer_bytes).map_err(|err| match err {
+        Error::BincodeDecode(err) => Error::CorruptFooter {
+            reason: err.to_string(),
+        },
+        err => err,
+    })?;
+    validate_footer(&footer, footer_start)?;
     Ok(footer)
 }
 
-pub fn decode_column_impl<T: 'static + bincode::Decode>(
-    r: impl Read + Seek,
-) -> Result<impl Iterator<Item = Result<rle::Values<T>>>> {
+pub fn find_column<'a>(footer: &'a Footer, name: &str) -> Result<&'a ColumnInfo> {
+    match footer.columns.iter().find(|c| c.name == name) {
+        Some(c) => Ok(c),
+        None => Err(Error::ColumnNotFound {
+            name: Some(name.to_string()),
+        }),
+    }
+}
+
+pub fn decode_column_impl<R, T>(
+    r: R,
+    data_start: u64,
+    column: ColumnInfo,
+    options: DecodeOptions,
+) -> Result<ColumnReader<R, T>>
+where
+    R: Read + Seek,
+    T: 'static + ColumnItem + Clone,
+    T::Value: FromColumnValue,
+{
+    if let Some(logical_type) = T::Value::logical_type() {
+        if column.logical_type.as_ref() != Some(&logical_type) {
+            return Err(Error::UnexpectedLogicalType {
+                logical_type: column.logical_type,
+                actual: std::any::type_name::<T>(),
+            });
+        }
+    }
+    if T::REPEATED != column.repeated {
+        return Err(Error::RepeatedMismatch {
+            repeated: column.repeated,
+            actual: std::any::type_name::<T>(),
+        });
+    }
     let mut r = r;
-    let data_start = r.stream_position()?;
-    let footer = decode_footer_impl(&mut r)?;
-    r.seek(std::io::SeekFrom::Start(data_start))?;
-    // TODO: Return an error instead of panicking.
-    if !footer.data_type.is_supported::<T>() {
-        return Err(UnsupportedDataType {
-            expected: footer.data_type,
+    let decode_values = values_decoder::<Physical<T>>(&mut r, data_start, &column, options)?;
+    if !T::NULLABLE && column.null_count > 0 {
+        return Err(Error::UnexpectedNulls {
+            null_count: column.null_count,
             actual: std::any::type_name::<T>(),
+        });
+    }
+    let decode_page: PageDecoder<T> = if T::REPEATED {
+        let decode_lists: PageDecoder<Vec<Physical<T>>> =
+            Box::new(move |r, page| decode_list_page(r, page, &decode_values));
+        Box::new(move |r, page| {
+            decode_optional_page(r, page, T::NULLABLE, &decode_lists)?
+                .into_iter()
+                .map(from_physical_list)
+                .collect()
+        })
+    } else {
+        Box::new(move |r, page| {
+            decode_optional_page(r, page, T::NULLABLE, &decode_values)?
+                .into_iter()
+                .map(from_physical)
+                .collect()
+        })
+    };
+    Ok(ColumnReader::new(r, data_start, column.pages, decode_page))
+}
+
+fn decode_optional_page<T: Clone>(
+    r: &mut &[u8],
+    page: &PageInfo,
+    nullable: bool,
+    decode_values: &PageDecoder<T>,
+) -> Result<Vec<rle::Values<Option<T>>>> {
+    if nullable {
+        return decode_nullable_page(r, page, decode_values);
+    }
+    let values = decode_values(r, page)?
+        .into_iter()
+        .map(|v| rle::Values {
+            value: Some(v.value),
+            run_length: v.run_length,
+        })
+        .collect();
+    Ok(values)
+}
+
+type Physical<T> = <<T as ColumnItem>::Value as ColumnValue>::Physical;
+
+fn from_physical<T>(values: rle::Values<Option<Physical<T>>>) -> Result<rle::Values<T>>
+where
+    T: ColumnItem,
+    T::Value: FromColumnValue,
+{
+    let value = values.value.map(T::Value::from_physical).transpose()?;
+    Ok(rle::Values {
+        value: T::from_value(value).expect("non-nullable columns are decoded without nulls"),
+        run_length: values.run_length,
+    })
+}
+
+// Returns a decoder for the pages of column without their validity bitmap.
+fn values_decoder<T: PhysicalValue>(
+    r: &mut (impl Read + Seek),
+    data_start: u64,
+    column: &ColumnInfo,
+    options: DecodeOptions,
+) -> Result<PageDecoder<T>> {
+    if let (Some(width), Some(actual_width)) = (column.integer_width, T::INTEGER_WIDTH) {
+        if width != actual_width {
+            return widened_values_decoder(r, data_start, column, options, width);
         }
-        .into());
     }
+    if column.data_type != T::DATA_TYPE {
+        return Err(Error::DataTypeMismatch {
+            expected: column.data_type,
+            actual: std::any::type_name::<T>(),
+        });
+    }
+    if column.data_type == DataType::Categorical {
+        return Ok(categories_decoder(column.categories.clone()));
+    }
+    let dictionary: Vec<T> = match &column.dictionary {
+        Some(d) => decode_dictionary(r, data_start, d)?,
+        None => Vec::new(),
+    };
+    Ok(Box::new(move |r, page| decode_page(r, page, &dictionary)))
+}
+// Format Overview:6 ends here
 
-    let mut iter_pages = footer.pages.into_iter().peekable();
-    let iter = std::iter::from_fn(move || -> Option<Result<rle::Values<T>>> {
-        // TODO: Verify
-        while iter_pages.next_if(|p| p.values_count == 0).is_some() {}
-        let page = iter_pages.peek_mut()?;
-        let rle_element_or_err = if footer.use_rle {
-            bincode::decode_from_std_read(&mut r, BINCODE_DATA_CONFIG)
-        } else {
-            bincode::decode_from_std_read(&mut r, BINCODE_DATA_CONFIG).map(rle::Values::single)
-        };
-        if let Ok(e) = &rle_element_or_err {
-            page.values_count -= e.run_length as usize;
+// [[file:../wills-columnar-format.org::#FormatSpecificationFormatOverview-j3k696o03tj0][Format Overview:7]]
+fn read_page(r: &mut impl Read, page: &PageInfo) -> Result<Vec<u8>> {
+    let compressed = read_page_bytes(r, page)?;
+    compression::decompress(page.compression, &compressed, page.uncompressed_size)
+}
+
+fn decode_page<T: PhysicalValue>(
+    r: &mut impl Read,
+    page: &PageInfo,
+    dictionary: &[T],
+) -> Result<Vec<rle::Values<T>>> {
+    let use_rle = page.use_rle;
+    let values: Vec<rle::Values<T>> = match page.encoding {
+        PageEncoding::Plain => (0..page.encoded_values_count)
+            .map(|_| decode_element(r, use_rle))
+            .collect::<Result<_>>()?,
+        PageEncoding::Dictionary => (0..page.encoded_values_count)
+            .map(|_| decode_dictionary_element(r, use_rle, dictionary))
+            .collect::<Result<_>>()?,
+        PageEncoding::Delta | PageEncoding::DeltaOfDelta => decode_delta_page(r, page, use_rle)?,
+        PageEncoding::BitPacked { min, bit_width } => {
+            decode_bit_packed_page(r, page, min, bit_width, use_rle)?
         }
-        Some(rle_element_or_err.map_err(std::convert::Into::into))
+        PageEncoding::ByteStreamSplit => decode_byte_stream_split_page(r, page)?,
+        PageEncoding::Xor => decode_xor_page(r, page)?,
+        PageEncoding::PackedBooleans => decode_packed_booleans_page(r, page)?,
+        PageEncoding::FixedSizeBinary { size } => decode_fixed_size_binary_page(r, page, size)?,
+    };
+    let values_count = values.iter().fold(0usize, |count, v| {
+        count.saturating_add(v.run_length as usize)
     });
-    Ok(iter)
+    if values_count != page.values_count {
+        return Err(Error::PageCountMismatch {
+            expected: page.values_count,
+            actual: values_count,
+        });
+    }
+    Ok(values)
 }
-// Format Overview:3 ends here
+
+fn decode_dictionary_element<T: bincode::Decode + Clone>(
+    r: &mut impl Read,
+    use_rle: bool,
+    dictionary: &[T],
+) -> Result<rle::Values<T>> {
+    let code: rle::Values<u64> = decode_element(r, use_rle)?;
+    match dictionary.get(code.value as usize) {
+        Some(v) => Ok(rle::Values {
+            value: v.clone(),
+            run_length: code.run_length,
+        }),
+        None => Err(Error::InvalidDictionaryCode {
+            code: code.value,
+            dictionary_size: dictionary.len(),
+        }),
+    }
+}
+
+fn decode_element<T: bincode::Decode>(r: &mut impl Read, use_rle: bool) -> Result<rle::Values<T>> {
+    let element = if use_rle {
+        bincode::decode_from_std_read(r, BINCODE_DATA_CONFIG)?
+    } else {
+        rle::Values::single(bincode::decode_from_std_read(r, BINCODE_DATA_CONFIG)?)
+    };
+    Ok(element)
+}
+// Format Overview:7 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationChecksums-v3p8n6j24tj0][Checksums:2]]
+pub fn read_page_bytes(r: &mut impl Read, page: &PageInfo) -> Result<Vec<u8>> {
+    let mut page_bytes = vec![0u8; page.compressed_size];
+    r.read_exact(&mut page_bytes)?;
+    verify_checksum(&page_bytes, page.checksum, page.file_offset)?;
+    Ok(page_bytes)
+}
+
+pub fn read_dictionary_bytes(r: &mut impl Read, dictionary: &DictionaryInfo) -> Result<Vec<u8>> {
+    let mut dictionary_bytes = vec![0u8; dictionary.size];
+    r.read_exact(&mut dictionary_bytes)?;
+    verify_checksum(
+        &dictionary_bytes,
+        dictionary.checksum,
+        dictionary.file_offset,
+    )?;
+    Ok(dictionary_bytes)
+}
+
+fn verify_checksum(bytes: &[u8], expected: u32, file_offset: i64) -> Result<()> {
+    let actual = crc32c::crc32c(bytes);
+    if actual != expected {
+        return Err(Error::ChecksumMismatch {
+            file_offset,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+// Checksums:2 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFooterValidation-w5k2r8p25tj0][Footer Validation:2]]
+fn validate_footer(footer: &Footer, footer_start: u64) -> Result<()> {
+    let mut names = HashSet::new();
+    // The end of the data of the previous column. Version 0 files have no
+    // leading magic bytes.
+    let mut data_end = if footer.version == 0 {
+        0
+    } else {
+        MAGIC_BYTES.len() as u64
+    };
+    for column in footer.columns.iter() {
+        if !names.insert(column.name.as_str()) {
+            return Err(Error::CorruptFooter {
+                reason: format!("column {:?} appears more than once", column.name),
+            });
+        }
+        for (idx, page) in column.pages.iter().enumerate() {
+            let file_offset = u64::try_from(page.file_offset).unwrap_or(u64::MAX);
+            if file_offset < data_end || (idx > 0 && file_offset != data_end) {
+                return Err(Error::CorruptFooter {
+                    reason: format!(
+                        "page {} of column {:?} starts at {} but the previous data ends at {}",
+                        idx, column.name, page.file_offset, data_end
+                    ),
+                });
+            }
+            data_end = match file_offset.checked_add(page.compressed_size as u64) {
+                Some(end) if end <= footer_start => end,
+                _ => {
+                    return Err(Error::CorruptFooter {
+                        reason: format!(
+                            "page {} of column {:?} ends past the footer at {}",
+                            idx, column.name, footer_start
+                        ),
+                    })
+                }
+            };
+            validate_page(page, column.data_type).map_err(|reason| Error::CorruptFooter {
+                reason: format!("page {} of column {:?} {}", idx, column.name, reason),
+            })?;
+        }
+        let null_count: usize = column.pages.iter().map(|p| p.null_count).sum();
+        if null_count != column.null_count {
+            return Err(Error::CorruptFooter {
+                reason: format!(
+                    "column {:?} has {} nulls but its pages have {}",
+                    column.name, column.null_count, null_count
+                ),
+            });
+        }
+        if let Some(dictionary) = &column.dictionary {
+            // Every value of the dictionary takes at least one byte.
+            let file_offset = u64::try_from(dictionary.file_offset).unwrap_or(u64::MAX);
+            let end = file_offset.saturating_add(dictionary.size as u64);
+            if file_offset < data_end
+                || end > footer_start
+                || dictionary.values_count > dictionary.size
+            {
+                return Err(Error::CorruptFooter {
+                    reason: format!(
+                        "dictionary of column {:?} with {} values at {} is outside of the data",
+                        column.name, dictionary.values_count, dictionary.file_offset
+                    ),
+                });
+            }
+            data_end = end;
+        }
+    }
+    Ok(())
+}
+// Footer Validation:2 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFooterValidation-w5k2r8p25tj0][Footer Validation:3]]
+fn validate_page(page: &PageInfo, data_type: DataType) -> std::result::Result<(), String> {
+    if !encoding_supports(page.encoding, data_type) {
+        return Err(format!(
+            "uses {:?} encoding which does not support {:?} values",
+            page.encoding, data_type
+        ));
+    }
+    if page.values_count > MAX_PAGE_VALUES || page.encoded_values_count > MAX_PAGE_VALUES {
+        return Err(format!(
+            "has {} values and {} encoded values but the maximum is {}",
+            page.values_count, page.encoded_values_count, MAX_PAGE_VALUES
+        ));
+    }
+    if page.null_count > page.values_count {
+        return Err(format!(
+            "has {} nulls but only {} values",
+            page.null_count, page.values_count
+        ));
+    }
+    if page.uncompressed_size > MAX_DECODE_ALLOCATION {
+        return Err(format!(
+            "decompresses to {} bytes but the maximum is {}",
+            page.uncompressed_size, MAX_DECODE_ALLOCATION
+        ));
+    }
+    if page.compression == Compression::None && page.uncompressed_size != page.compressed_size {
+        return Err(format!(
+            "is not compressed but has {} compressed bytes and {} uncompressed bytes",
+            page.compressed_size, page.uncompressed_size
+        ));
+    }
+    match page.encoding {
+        // Each encoded value takes at least one byte.
+        PageEncoding::Plain
+        | PageEncoding::Dictionary
+        | PageEncoding::Delta
+        | PageEncoding::DeltaOfDelta
+            if page.encoded_values_count > page.uncompressed_size =>
+        {
+            Err(format!(
+                "has {} encoded values in only {} bytes",
+                page.encoded_values_count, page.uncompressed_size
+            ))
+        }
+        PageEncoding::BitPacked { bit_width, .. } if bit_width > 128 => {
+            Err(format!("has a bit width of {}", bit_width))
+        }
+        PageEncoding::FixedSizeBinary { size } if size > MAX_DECODE_ALLOCATION => {
+            Err(format!("has values of {} bytes", size))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn encoding_supports(encoding: PageEncoding, data_type: DataType) -> bool {
+    // Categorical columns store their codes as unsigned integers.
+    let is_integer = data_type.is_integer() || data_type == DataType::Categorical;
+    match encoding {
+        PageEncoding::Plain | PageEncoding::Dictionary => true,
+        PageEncoding::Delta | PageEncoding::DeltaOfDelta | PageEncoding::BitPacked { .. } => {
+            is_integer
+        }
+        PageEncoding::ByteStreamSplit | PageEncoding::Xor => data_type.is_float(),
+        PageEncoding::PackedBooleans => data_type == DataType::Boolean,
+        PageEncoding::FixedSizeBinary { .. } => data_type == DataType::Binary,
+    }
+}
+// Footer Validation:3 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFooterValidation-w5k2r8p25tj0][Footer Validation:4]]
+fn read_bytes(r: &mut impl Read, len: usize) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    r.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(bytes)
+}
+// Footer Validation:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDictionaryEncoding-p4x1c8e07tj0][Dictionary Encoding:4]]
+fn decode_dictionary<T: bincode::Decode>(
+    r: &mut (impl Read + Seek),
+    data_start: u64,
+    dictionary: &DictionaryInfo,
+) -> Result<Vec<T>> {
+    r.seek(std::io::SeekFrom::Start(
+        data_start + dictionary.file_offset as u64,
+    ))?;
+    let dictionary_bytes = read_dictionary_bytes(r, dictionary)?;
+    let mut dictionary_bytes = dictionary_bytes.as_slice();
+    let mut values = Vec::with_capacity(dictionary.values_count);
+    for _ in 0..dictionary.values_count {
+        values.push(bincode::decode_from_std_read(
+            &mut dictionary_bytes,
+            BINCODE_DATA_CONFIG,
+        )?);
+    }
+    Ok(values)
+}
+// Dictionary Encoding:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDeltaEncoding-h7t3n2a08tj0][Delta Encoding:6]]
+fn decode_delta_page<T: PhysicalValue>(
+    r: &mut impl Read,
+    page: &PageInfo,
+    use_rle: bool,
+) -> Result<Vec<rle::Values<T>>> {
+    let mut decoder = DeltaDecoder::new(page.encoding == PageEncoding::DeltaOfDelta);
+    let mut values: Vec<rle::Values<T>> = Vec::with_capacity(page.encoded_values_count);
+    let mut previous_value = None;
+    let mut decoded_count = 0usize;
+    for _ in 0..page.encoded_values_count {
+        let encoded: rle::Values<u128> = decode_element(r, use_rle)?;
+        decoded_count = decoded_count.saturating_add(encoded.run_length as usize);
+        if decoded_count > page.values_count {
+            return Err(Error::PageCountMismatch {
+                expected: page.values_count,
+                actual: decoded_count,
+            });
+        }
+        for _ in 0..encoded.run_length {
+            let value = decoder.decode(encoded.value);
+            push_integer(&mut values, &mut previous_value, value, use_rle)?;
+        }
+    }
+    Ok(values)
+}
+
+fn push_integer<T: PhysicalValue>(
+    values: &mut Vec<rle::Values<T>>,
+    previous_value: &mut Option<i128>,
+    value: i128,
+    use_rle: bool,
+) -> Result<()> {
+    if use_rle && *previous_value == Some(value) {
+        values.last_mut().unwrap().run_length += 1;
+        return Ok(());
+    }
+    *previous_value = Some(value);
+    match T::from_i128(value) {
+        Some(v) => {
+            values.push(rle::Values::single(v));
+            Ok(())
+        }
+        None => Err(Error::IntegerOutOfRange {
+            value,
+            actual: std::any::type_name::<T>(),
+        }),
+    }
+}
+// Delta Encoding:6 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBitPacking-r5d9k3c08tj0][Bit Packing:4]]
+fn decode_bit_packed_page<T: PhysicalValue>(
+    r: &mut impl Read,
+    page: &PageInfo,
+    min: i128,
+    bit_width: u8,
+    use_rle: bool,
+) -> Result<Vec<rle::Values<T>>> {
+    let packed = read_bytes(r, bitpack::packed_size(page.values_count, bit_width))?;
+    let mut values = Vec::with_capacity(page.values_count);
+    let mut previous_value = None;
+    for offset in bitpack::unpack(&packed, bit_width, page.values_count) {
+        let value = min.wrapping_add(offset as i128);
+        push_integer(&mut values, &mut previous_value, value, use_rle)?;
+    }
+    Ok(values)
+}
+// Bit Packing:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingFloatingPointEncoding-b6s2e9x14tj0][Floating Point Encoding:7]]
+fn decode_byte_stream_split_page<T: PhysicalValue>(
+    r: &mut impl Read,
+    page: &PageInfo,
+) -> Result<Vec<rle::Values<T>>> {
+    let byte_width = std::mem::size_of::<T>();
+    let streams = read_bytes(r, page.values_count * byte_width)?;
+    float::join_streams(&streams, byte_width, page.values_count)
+        .into_iter()
+        .map(|bits| decode_float(bits, page))
+        .collect()
+}
+
+fn decode_float<T: PhysicalValue>(bits: u64, page: &PageInfo) -> Result<rle::Values<T>> {
+    match T::from_float_bits(bits) {
+        Some(v) => Ok(rle::Values::single(v)),
+        None => Err(page_type_mismatch::<T>(page)),
+    }
+}
+
+fn page_type_mismatch<T>(page: &PageInfo) -> Error {
+    Error::CorruptFooter {
+        reason: format!(
+            "{:?} page can not be decoded into {}",
+            page.encoding,
+            std::any::type_name::<T>()
+        ),
+    }
+}
+// Floating Point Encoding:7 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingXorEncoding-c4j8u1t14tj0][XOR Encoding:3]]
+fn decode_xor_page<T: PhysicalValue>(
+    r: &mut impl Read,
+    page: &PageInfo,
+) -> Result<Vec<rle::Values<T>>> {
+    let bit_width = std::mem::size_of::<T>() as u32 * 8;
+    let mut encoded = Vec::new();
+    r.read_to_end(&mut encoded)?;
+    xor::decode(&encoded, bit_width, page.values_count)?
+        .into_iter()
+        .map(|bits| decode_float(bits, page))
+        .collect()
+}
+// XOR Encoding:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBooleanEncoding-a7x4m2g15tj0][Boolean Encoding:5]]
+fn decode_packed_booleans_page<T: PhysicalValue>(
+    r: &mut impl Read,
+    page: &PageInfo,
+) -> Result<Vec<rle::Values<T>>> {
+    boolean::decode(r, page.values_count)?
+        .into_iter()
+        .map(|v| match T::from_bool(v.value) {
+            Some(value) => Ok(rle::Values {
+                value,
+                run_length: v.run_length,
+            }),
+            None => Err(page_type_mismatch::<T>(page)),
+        })
+        .collect()
+}
+// Boolean Encoding:5 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBinaryEncoding-f3z8r6d16tj0][Binary Encoding:4]]
+fn decode_fixed_size_binary_page<T: PhysicalValue>(
+    r: &mut impl Read,
+    page: &PageInfo,
+    size: usize,
+) -> Result<Vec<rle::Values<T>>> {
+    let bytes = read_bytes(r, page.values_count.saturating_mul(size))?;
+    (0..page.values_count)
+        .map(
+            |idx| match T::from_bytes(&bytes[idx * size..(idx + 1) * size]) {
+                Some(v) => Ok(rle::Values::single(v)),
+                None => Err(page_type_mismatch::<T>(page)),
+            },
+        )
+        .collect()
+}
+// Binary Encoding:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingNullableColumns-n5k2w8p13tj0][Nullable Columns:3]]
+fn decode_nullable_page<T: Clone>(
+    r: &mut &[u8],
+    page: &PageInfo,
+    decode_values: &PageDecoder<T>,
+) -> Result<Vec<rle::Values<Option<T>>>> {
+    let non_null_count =
+        page.values_count
+            .checked_sub(page.null_count)
+            .ok_or(Error::PageCountMismatch {
+                expected: page.values_count,
+                actual: page.null_count,
+            })?;
+    let validity: Vec<bool> = if page.null_count == 0 {
+        vec![true; page.values_count]
+    } else {
+        let mut packed = vec![0u8; bitpack::packed_size(page.values_count, 1)];
+        r.read_exact(&mut packed)?;
+        bitpack::unpack(&packed, 1, page.values_count)
+            .into_iter()
+            .map(|bit| bit == 1)
+            .collect()
+    };
+    let valid_count = validity.iter().filter(|is_valid| **is_valid).count();
+    if valid_count != non_null_count {
+        return Err(Error::PageCountMismatch {
+            expected: non_null_count,
+            actual: valid_count,
+        });
+    }
+    let non_null_page = PageInfo {
+        values_count: non_null_count,
+        null_count: 0,
+        ..*page
+    };
+    let mut non_null_values: VecDeque<_> = decode_values(r, &non_null_page)?.into();
+    let mut values = Vec::new();
+    for (count, is_valid) in validity.into_iter().dedup_with_count() {
+        let mut remaining = count as u64;
+        if !is_valid {
+            values.push(rle::Values {
+                value: None,
+                run_length: remaining,
+            });
+            continue;
+        }
+        while remaining > 0 {
+            let front = non_null_values
+                .front_mut()
+                .expect("valid count matches the non-null values");
+            let run_length = front.run_length.min(remaining);
+            values.push(rle::Values {
+                value: Some(front.value.clone()),
+                run_length,
+            });
+            front.run_length -= run_length;
+            remaining -= run_length;
+            if front.run_length == 0 {
+                non_null_values.pop_front();
+            }
+        }
+    }
+    Ok(values)
+}
+// Nullable Columns:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingListColumns-c8r4j2w19tj0][List Columns:4]]
+fn decode_list_page<T: Clone>(
+    r: &mut &[u8],
+    page: &PageInfo,
+    decode_values: &PageDecoder<T>,
+) -> Result<Vec<rle::Values<Vec<T>>>> {
+    let mut lengths: Vec<usize> = Vec::with_capacity(page.values_count);
+    while lengths.len() < page.values_count {
+        let run: rle::Values<u64> = decode_element(r, true)?;
+        let run_length = run.run_length as usize;
+        if run_length > page.values_count - lengths.len() {
+            return Err(Error::PageCountMismatch {
+                expected: page.values_count,
+                actual: lengths.len() + run_length,
+            });
+        }
+        lengths.extend(std::iter::repeat_n(run.value as usize, run_length));
+    }
+    let values_count = lengths
+        .iter()
+        .fold(0usize, |count, len| count.saturating_add(*len));
+    if values_count > MAX_PAGE_VALUES {
+        return Err(Error::PageTooLarge { values_count });
+    }
+    let values_page = PageInfo {
+        values_count,
+        null_count: 0,
+        ..*page
+    };
+    let mut values = decode_values(r, &values_page)?
+        .into_iter()
+        .flat_map(|v| std::iter::repeat_n(v.value, v.run_length as usize));
+    let lists = lengths
+        .into_iter()
+        .map(|len| rle::Values::single(values.by_ref().take(len).collect()))
+        .collect();
+    Ok(lists)
+}
+
+fn from_physical_list<T>(values: rle::Values<Option<Vec<Physical<T>>>>) -> Result<rle::Values<T>>
+where
+    T: ColumnItem,
+    T::Value: FromColumnValue,
+{
+    let value = match values.value {
+        Some(list) => Some(
+            list.into_iter()
+                .map(T::Value::from_physical)
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        None => None,
+    };
+    Ok(rle::Values {
+        value: T::from_values(value).expect("list columns are decoded as lists"),
+        run_length: values.run_length,
+    })
+}
+// List Columns:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCategoricalColumns-s9k3d6w22tj0][Categorical Columns:4]]
+fn categories_decoder<T: PhysicalValue>(categories: Vec<String>) -> PageDecoder<T> {
+    Box::new(move |r, page| {
+        decode_page::<u32>(r, page, &[])?
+            .into_iter()
+            .map(|v| match categories.get(v.value as usize) {
+                Some(label) => Ok(rle::Values {
+                    value: T::from_category(v.value, label).expect("categorical data type"),
+                    run_length: v.run_length,
+                }),
+                None => Err(Error::InvalidCategoryCode {
+                    code: v.value,
+                    categories_count: categories.len(),
+                }),
+            })
+            .collect()
+    })
+}
+// Categorical Columns:4 ends here
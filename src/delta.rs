@@ -0,0 +1,64 @@
+// [[file:../wills-columnar-format.org::#DataEncodingDeltaEncoding-h7t3n2a08tj0][Delta Encoding:2]]
+pub fn zigzag_encode(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+pub fn zigzag_decode(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
+// Delta Encoding:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDeltaEncoding-h7t3n2a08tj0][Delta Encoding:3]]
+pub struct DeltaEncoder {
+    delta_of_delta: bool,
+    previous_value: i128,
+    previous_delta: i128,
+}
+
+impl DeltaEncoder {
+    pub fn new(delta_of_delta: bool) -> DeltaEncoder {
+        DeltaEncoder {
+            delta_of_delta,
+            previous_value: 0,
+            previous_delta: 0,
+        }
+    }
+
+    pub fn encode(&mut self, value: i128) -> u128 {
+        let delta = value.wrapping_sub(self.previous_value);
+        self.previous_value = value;
+        if !self.delta_of_delta {
+            return zigzag_encode(delta);
+        }
+        let delta_of_delta = delta.wrapping_sub(self.previous_delta);
+        self.previous_delta = delta;
+        zigzag_encode(delta_of_delta)
+    }
+}
+
+pub struct DeltaDecoder {
+    delta_of_delta: bool,
+    previous_value: i128,
+    previous_delta: i128,
+}
+
+impl DeltaDecoder {
+    pub fn new(delta_of_delta: bool) -> DeltaDecoder {
+        DeltaDecoder {
+            delta_of_delta,
+            previous_value: 0,
+            previous_delta: 0,
+        }
+    }
+
+    pub fn decode(&mut self, encoded: u128) -> i128 {
+        let mut delta = zigzag_decode(encoded);
+        if self.delta_of_delta {
+            delta = delta.wrapping_add(self.previous_delta);
+            self.previous_delta = delta;
+        }
+        self.previous_value = self.previous_value.wrapping_add(delta);
+        self.previous_value
+    }
+}
+// Delta Encoding:3 ends here
@@ -0,0 +1,82 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:5]]
+use std::collections::HashMap;
+
+use crate::{Result, BINCODE_DATA_CONFIG};
+// Dependencies:5 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDictionaryEncoding-p4x1c8e07tj0][Dictionary Encoding:2]]
+pub struct DictionaryBuilder {
+    codes: HashMap<Vec<u8>, u64>,
+    encoded_values: Vec<u8>,
+    // The start of each value within encoded_values, indexed by code.
+    offsets: Vec<usize>,
+    max_size: usize,
+    is_full: bool,
+}
+
+impl DictionaryBuilder {
+    pub fn new(max_size: usize) -> DictionaryBuilder {
+        DictionaryBuilder {
+            codes: HashMap::new(),
+            encoded_values: Vec::new(),
+            offsets: Vec::new(),
+            max_size,
+            is_full: false,
+        }
+    }
+
+    // Returns the code for value. If value is not yet in the dictionary and
+    // there is no more space, then None is returned and the dictionary is
+    // marked as full.
+    pub fn code_for<T: bincode::Encode>(&mut self, value: &T) -> Result<Option<u64>> {
+        let encoded = bincode::encode_to_vec(value, BINCODE_DATA_CONFIG)?;
+        if let Some(code) = self.codes.get(&encoded) {
+            return Ok(Some(*code));
+        }
+        if self.is_full || self.encoded_values.len() + encoded.len() > self.max_size {
+            self.is_full = true;
+            return Ok(None);
+        }
+        let code = self.codes.len() as u64;
+        self.offsets.push(self.encoded_values.len());
+        self.encoded_values.extend_from_slice(&encoded);
+        self.codes.insert(encoded, code);
+        Ok(Some(code))
+    }
+
+    // Removes all values with a code greater than or equal to len.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        for code in len..self.len() {
+            let end = self
+                .offsets
+                .get(code + 1)
+                .copied()
+                .unwrap_or(self.encoded_values.len());
+            self.codes
+                .remove(&self.encoded_values[self.offsets[code]..end]);
+        }
+        self.encoded_values.truncate(self.offsets[len]);
+        self.offsets.truncate(len);
+    }
+
+    #[cfg(test)]
+    pub fn is_full(&self) -> bool {
+        self.is_full
+    }
+
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    pub fn encoded_values(&self) -> &[u8] {
+        &self.encoded_values
+    }
+}
+// Dictionary Encoding:2 ends here
@@ -1,101 +1,692 @@
-// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:6]]
-use std::io::Write;
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:7]]
+use std::{collections::HashMap, io::Write, iter::Peekable};
 
-use crate::{rle, DataType, Footer, PageInfo, Result, BINCODE_DATA_CONFIG};
-// Dependencies:6 ends here
+use crate::{
+    binary, bitpack, boolean, compression, delta::DeltaEncoder, dictionary::DictionaryBuilder,
+    float, rle, xor, ColumnInfo, ColumnItem, ColumnValue, Compression, DataType, DictionaryInfo,
+    EncodeOptions, Error, Footer, PageEncoding, PageInfo, PhysicalValue, Result,
+    BINCODE_DATA_CONFIG, MAGIC_BYTES, MAX_DECODE_ALLOCATION, MAX_PAGE_VALUES,
+};
+// Dependencies:7 ends here
 
-// [[file:../wills-columnar-format.org::#FormatSpecificationFormatOverview-j3k696o03tj0][Format Overview:2]]
+// [[file:../wills-columnar-format.org::#FormatSpecificationFormatOverview-j3k696o03tj0][Format Overview:5]]
 pub fn encode_column_impl<T>(
     w: &mut impl Write,
+    name: &str,
     values_iter: impl Iterator<Item = T>,
-    use_rle: bool,
-) -> Result<Footer>
+    file_offset: &mut i64,
+    options: EncodeOptions,
+) -> Result<ColumnInfo>
+where
+    T: ColumnItem,
+{
+    let mut options = options;
+    if options.logical_type.is_none() {
+        options.logical_type = T::Value::logical_type();
+    }
+    if T::REPEATED {
+        let rows = values_iter.map(|v| {
+            v.values()
+                .map(|values| values.iter().map(ColumnValue::to_physical).collect())
+        });
+        return encode_column_values::<Physical<T>, Vec<_>>(
+            w,
+            name,
+            rows,
+            file_offset,
+            options,
+            T::NULLABLE,
+            true,
+        );
+    }
+    // Items that are not repeated have exactly one value.
+    let values_iter = values_iter.map(|v| v.values().map(|values| values[0].to_physical()));
+    encode_column_values::<Physical<T>, _>(
+        w,
+        name,
+        values_iter,
+        file_offset,
+        options,
+        T::NULLABLE,
+        false,
+    )
+}
+
+type Physical<T> = <<T as ColumnItem>::Value as ColumnValue>::Physical;
+
+fn encode_column_values<T, R>(
+    w: &mut impl Write,
+    name: &str,
+    values_iter: impl Iterator<Item = Option<R>>,
+    file_offset: &mut i64,
+    options: EncodeOptions,
+    nullable: bool,
+    repeated: bool,
+) -> Result<ColumnInfo>
 where
-    T: 'static + bincode::Encode + Eq,
+    T: PhysicalValue,
+    R: Row<T>,
 {
-    // TODO: Return an error.
-    let data_type = DataType::from_type::<T>().expect("unsupported data type");
-    let mut values_iter = values_iter;
+    let data_type = T::DATA_TYPE;
+    let integer_width = T::INTEGER_WIDTH;
+    if let Some(logical_type) = &options.logical_type {
+        if !logical_type.is_supported(data_type, integer_width) {
+            return Err(Error::LogicalTypeMismatch {
+                logical_type: logical_type.clone(),
+                data_type,
+            });
+        }
+    }
+    let mut dictionary = if options.use_dictionary && data_type != DataType::Categorical {
+        Some(DictionaryBuilder::new(
+            options.max_dictionary_size.min(MAX_DECODE_ALLOCATION),
+        ))
+    } else {
+        None
+    };
+    let mut categories = CategoriesBuilder::default();
 
+    let mut values_iter = values_iter.peekable();
     let mut pages = Vec::new();
-    let mut file_offset = 0;
     loop {
-        let encoding =
-            encode_values_as_bincode(&mut values_iter, file_offset, MIN_TARGET_PAGE_SIZE, use_rle)?;
-        if encoding.encoded_values.is_empty() {
+        let page_values =
+            next_page_values(&mut values_iter, TARGET_PAGE_ROWS, MAX_TARGET_PAGE_SIZE)?;
+        if page_values.is_empty() {
             break;
+        }
+        let validity: Vec<bool> = page_values.iter().map(Option::is_some).collect();
+        let mut non_null_values: Vec<T> = Vec::new();
+        let mut lengths = Vec::new();
+        for row in page_values.into_iter().flatten() {
+            lengths.push(row.append_to(&mut non_null_values) as u64);
+        }
+        if non_null_values.len() > MAX_PAGE_VALUES {
+            return Err(Error::PageTooLarge {
+                values_count: non_null_values.len(),
+            });
+        }
+        if let Some(logical_type) = &options.logical_type {
+            for value in non_null_values.iter() {
+                logical_type.validate_value(value)?;
+            }
+        }
+        let encoding = if data_type == DataType::Categorical {
+            let codes = categories.codes_for(&non_null_values);
+            encode_page(
+                &codes,
+                *file_offset,
+                &options,
+                DataType::UnsignedInteger,
+                None,
+            )?
+        } else {
+            encode_page(
+                &non_null_values,
+                *file_offset,
+                &options,
+                data_type,
+                dictionary.as_mut(),
+            )?
+        };
+        let encoding = if repeated {
+            add_lengths(encoding, &lengths)?
         } else {
-            file_offset += w.write(encoding.encoded_values.as_slice())? as i64;
-            pages.push(encoding.page_info);
+            encoding
+        };
+        let encoding = add_validity(encoding, &validity);
+        if encoding.encoded_values.len() > MAX_DECODE_ALLOCATION {
+            return Err(Error::PageSizeTooLarge {
+                size: encoding.encoded_values.len(),
+            });
         }
+        let encoding = compress_page(encoding, options.compression)?;
+        let encoding = add_checksum(encoding);
+        w.write_all(encoding.encoded_values.as_slice())?;
+        *file_offset += encoding.encoded_values.len() as i64;
+        pages.push(encoding.page_info);
     }
-    let footer = Footer {
+    let dictionary = match dictionary {
+        Some(d) if !d.is_empty() => {
+            let dictionary_info = DictionaryInfo {
+                file_offset: *file_offset,
+                values_count: d.len(),
+                size: d.encoded_values().len(),
+                checksum: crc32c::crc32c(d.encoded_values()),
+            };
+            w.write_all(d.encoded_values())?;
+            *file_offset += d.encoded_values().len() as i64;
+            Some(dictionary_info)
+        }
+        _ => None,
+    };
+    Ok(ColumnInfo {
+        name: name.to_string(),
         data_type,
-        use_rle,
+        integer_width,
+        logical_type: options.logical_type,
+        nullable,
+        repeated,
+        null_count: pages.iter().map(|p| p.null_count).sum(),
+        dictionary,
+        categories: categories.labels,
         pages,
-    };
-    let footer_size = bincode::encode_into_std_write(&footer, w, BINCODE_DATA_CONFIG)? as u64;
-    w.write(&footer_size.to_le_bytes())?;
-    Ok(footer)
+    })
+}
+
+pub fn encode_footer_impl(w: &mut impl Write, footer: &Footer) -> Result<()> {
+    let footer_bytes = bincode::encode_to_vec(footer, BINCODE_DATA_CONFIG)?;
+    w.write_all(&footer_bytes)?;
+    w.write_all(&crc32c::crc32c(&footer_bytes).to_le_bytes())?;
+    let footer_size = footer_bytes.len() as u64;
+    w.write_all(&footer_size.to_le_bytes())?;
+    w.write_all(&MAGIC_BYTES)?;
+    Ok(())
 }
-// Format Overview:2 ends here
+// Format Overview:5 ends here
 
 // [[file:../wills-columnar-format.org::#FormatSpecificationPages-b9u4ccg05tj0][Pages:2]]
-const MIN_TARGET_PAGE_SIZE: usize = 2048;
+const TARGET_PAGE_ROWS: usize = 8192;
+const MAX_TARGET_PAGE_SIZE: usize = 1 << 20;
+
+fn next_page_values<T: bincode::Encode>(
+    values: &mut Peekable<impl Iterator<Item = T>>,
+    target_rows: usize,
+    max_encoded_size: usize,
+) -> Result<Vec<T>> {
+    let mut page_values = Vec::new();
+    let mut encoded_size = 0;
+    let mut encoded_value = Vec::new();
+    while page_values.len() < target_rows {
+        let Some(value) = values.peek() else {
+            break;
+        };
+        encoded_value.clear();
+        bincode::encode_into_std_write(value, &mut encoded_value, BINCODE_DATA_CONFIG)?;
+        if !page_values.is_empty() && encoded_size + encoded_value.len() > max_encoded_size {
+            break;
+        }
+        encoded_size += encoded_value.len();
+        page_values.extend(values.next());
+    }
+    Ok(page_values)
+}
 // Pages:2 ends here
 
+// [[file:../wills-columnar-format.org::#FormatSpecificationChecksums-v3p8n6j24tj0][Checksums:1]]
+fn add_checksum(encoding: Encoding) -> Encoding {
+    Encoding {
+        page_info: PageInfo {
+            checksum: crc32c::crc32c(&encoding.encoded_values),
+            ..encoding.page_info
+        },
+        encoded_values: encoding.encoded_values,
+    }
+}
+// Checksums:1 ends here
+
 // [[file:../wills-columnar-format.org::#DataEncodingBasicEncoding-e4m696o03tj0][Basic Encoding:2]]
 struct Encoding {
     pub encoded_values: Vec<u8>,
     pub page_info: PageInfo,
 }
 
-fn encode_values_as_bincode<T: 'static + bincode::Encode>(
-    values: &mut impl Iterator<Item = T>,
-    file_offset: i64,
-    target_encoded_size: usize,
-    use_rle: bool,
-) -> Result<Encoding>
+fn encode_values_as_bincode<T>(values: &[T], file_offset: i64, use_rle: bool) -> Result<Encoding>
+where
+    T: PhysicalValue,
+{
+    let (encoded_values, encoded_values_count) =
+        encode_elements(values.iter().map(float::TotalEq), use_rle)?;
+    Ok(Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count,
+            encoding: PageEncoding::Plain,
+            use_rle,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    })
+}
+// Basic Encoding:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBasicEncoding-e4m696o03tj0][Basic Encoding:3]]
+fn encode_elements<T>(values: impl Iterator<Item = T>, use_rle: bool) -> Result<(Vec<u8>, usize)>
 where
-    T: 'static + bincode::Encode + Eq,
+    T: bincode::Encode + Eq,
 {
     let mut encoded_values = Vec::new();
+    let mut encoded_values_count = 0;
     if use_rle {
-        let mut values_count = 0;
-        let mut encoded_values_count = 0;
         for rle in rle::encode_iter(values) {
-            values_count += rle.run_length as usize;
             encoded_values_count += 1;
             bincode::encode_into_std_write(rle, &mut encoded_values, BINCODE_DATA_CONFIG)?;
-            if encoded_values.len() >= target_encoded_size {
-                break;
-            }
         }
-        Ok(Encoding {
-            encoded_values,
-            page_info: PageInfo {
-                file_offset,
-                values_count,
-                encoded_values_count,
-            },
-        })
     } else {
-        let mut values_count = 0;
         for value in values {
-            values_count += 1;
+            encoded_values_count += 1;
             bincode::encode_into_std_write(value, &mut encoded_values, BINCODE_DATA_CONFIG)?;
-            if encoded_values.len() >= target_encoded_size {
-                break;
+        }
+    }
+    Ok((encoded_values, encoded_values_count))
+}
+// Basic Encoding:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDictionaryEncoding-p4x1c8e07tj0][Dictionary Encoding:3]]
+fn encode_values_with_dictionary<T>(
+    values: &[T],
+    file_offset: i64,
+    use_rle: bool,
+    dictionary: &mut DictionaryBuilder,
+) -> Result<Option<Encoding>>
+where
+    T: PhysicalValue,
+{
+    let mut codes = Vec::with_capacity(values.len());
+    for value in values {
+        match dictionary.code_for(value)? {
+            Some(code) => codes.push(code),
+            None => return Ok(None),
+        }
+    }
+    let (encoded_values, encoded_values_count) = encode_elements(codes.into_iter(), use_rle)?;
+    Ok(Some(Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count,
+            encoding: PageEncoding::Dictionary,
+            use_rle,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    }))
+}
+// Dictionary Encoding:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDeltaEncoding-h7t3n2a08tj0][Delta Encoding:5]]
+fn encode_values_as_delta(
+    values: &[i128],
+    file_offset: i64,
+    use_rle: bool,
+    delta_of_delta: bool,
+) -> Result<Encoding> {
+    let mut encoder = DeltaEncoder::new(delta_of_delta);
+    let deltas = values.iter().map(|v| encoder.encode(*v));
+    let (encoded_values, encoded_values_count) = encode_elements(deltas, use_rle)?;
+    let encoding = if delta_of_delta {
+        PageEncoding::DeltaOfDelta
+    } else {
+        PageEncoding::Delta
+    };
+    Ok(Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count,
+            encoding,
+            use_rle,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    })
+}
+// Delta Encoding:5 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBitPacking-r5d9k3c08tj0][Bit Packing:3]]
+fn encode_values_as_bit_packed(values: &[i128], file_offset: i64) -> Encoding {
+    let min = values.iter().copied().min().unwrap_or(0);
+    let offsets: Vec<u128> = values.iter().map(|v| v.wrapping_sub(min) as u128).collect();
+    let bit_width = offsets.iter().copied().max().map_or(0, bitpack::bit_width);
+    let encoded_values = bitpack::pack(&offsets, bit_width);
+    Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count: values.len(),
+            encoding: PageEncoding::BitPacked { min, bit_width },
+            use_rle: false,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    }
+}
+// Bit Packing:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingFloatingPointEncoding-b6s2e9x14tj0][Floating Point Encoding:6]]
+fn encode_values_as_byte_stream_split(
+    values: &[u64],
+    byte_width: usize,
+    file_offset: i64,
+) -> Encoding {
+    let encoded_values = float::split_streams(values, byte_width);
+    Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count: values.len(),
+            encoding: PageEncoding::ByteStreamSplit,
+            use_rle: false,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    }
+}
+// Floating Point Encoding:6 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingXorEncoding-c4j8u1t14tj0][XOR Encoding:2]]
+fn encode_values_as_xor(values: &[u64], byte_width: usize, file_offset: i64) -> Encoding {
+    let encoded_values = xor::encode(values, byte_width as u32 * 8);
+    Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count: values.len(),
+            encoding: PageEncoding::Xor,
+            use_rle: false,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    }
+}
+// XOR Encoding:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBooleanEncoding-a7x4m2g15tj0][Boolean Encoding:4]]
+fn encode_values_as_packed_booleans(values: &[bool], file_offset: i64) -> Result<Encoding> {
+    let encoded_values = boolean::encode(values)?;
+    Ok(Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count: values.len(),
+            encoding: PageEncoding::PackedBooleans,
+            use_rle: false,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    })
+}
+// Boolean Encoding:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBinaryEncoding-f3z8r6d16tj0][Binary Encoding:3]]
+fn encode_values_as_fixed_size_binary(values: &[&[u8]], file_offset: i64) -> Option<Encoding> {
+    let size = binary::fixed_size(values)?;
+    let encoded_values = values.concat();
+    Some(Encoding {
+        page_info: PageInfo {
+            file_offset,
+            values_count: values.len(),
+            null_count: 0,
+            encoded_values_count: values.len(),
+            encoding: PageEncoding::FixedSizeBinary { size },
+            use_rle: false,
+            compression: Compression::None,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            checksum: 0,
+        },
+        encoded_values,
+    })
+}
+// Binary Encoding:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingAutomaticEncodingSelection-f8b2w6e09tj0][Automatic Encoding Selection:1]]
+fn encode_page<T>(
+    values: &[T],
+    file_offset: i64,
+    options: &EncodeOptions,
+    data_type: DataType,
+    dictionary: Option<&mut DictionaryBuilder>,
+) -> Result<Encoding>
+where
+    T: PhysicalValue,
+{
+    let rle_options: &[bool] = if options.use_rle {
+        &[false, true]
+    } else {
+        &[false]
+    };
+    let mut candidates = Vec::new();
+    for use_rle in rle_options.iter().copied() {
+        candidates.push(encode_values_as_bincode(values, file_offset, use_rle)?);
+    }
+    if data_type.is_integer() && (options.use_delta || options.use_bit_packing) {
+        let values: Vec<i128> = values
+            .iter()
+            .map(|v| v.to_i128().expect("integer data type"))
+            .collect();
+        if options.use_delta {
+            for delta_of_delta in [false, true] {
+                for use_rle in rle_options.iter().copied() {
+                    candidates.push(encode_values_as_delta(
+                        &values,
+                        file_offset,
+                        use_rle,
+                        delta_of_delta,
+                    )?);
+                }
             }
         }
-        Ok(Encoding {
-            encoded_values,
-            page_info: PageInfo {
-                file_offset,
-                values_count,
-                encoded_values_count: values_count,
-            },
-        })
+        if options.use_bit_packing {
+            candidates.push(encode_values_as_bit_packed(&values, file_offset));
+        }
+    }
+    if data_type.is_float() && (options.use_byte_stream_split || options.use_xor) {
+        let byte_width = std::mem::size_of::<T>();
+        let values: Vec<u64> = values
+            .iter()
+            .map(|v| v.to_float_bits().expect("float data type"))
+            .collect();
+        if options.use_xor {
+            candidates.push(encode_values_as_xor(&values, byte_width, file_offset));
+        }
+        // Byte stream split is the same size as plain encoding but compresses
+        // better. It goes first so that it wins the tie.
+        if options.use_byte_stream_split && options.compression != Compression::None {
+            candidates.insert(
+                0,
+                encode_values_as_byte_stream_split(&values, byte_width, file_offset),
+            );
+        }
+    }
+    if data_type == DataType::Boolean && options.use_bit_packing {
+        let values: Vec<bool> = values
+            .iter()
+            .map(|v| v.to_bool().expect("boolean data type"))
+            .collect();
+        candidates.push(encode_values_as_packed_booleans(&values, file_offset)?);
+    }
+    if data_type == DataType::Binary && options.use_fixed_size_binary {
+        let values: Vec<&[u8]> = values
+            .iter()
+            .map(|v| v.as_bytes().expect("binary data type"))
+            .collect();
+        if let Some(encoding) = encode_values_as_fixed_size_binary(&values, file_offset) {
+            candidates.push(encoding);
+        }
+    }
+    let best = candidates
+        .into_iter()
+        .min_by_key(|c| c.encoded_values.len())
+        .expect("plain encoding is always a candidate");
+    match dictionary {
+        Some(dictionary) => {
+            encode_page_with_dictionary(values, file_offset, rle_options, dictionary, best)
+        }
+        None => Ok(best),
     }
 }
-// Basic Encoding:2 ends here
+// Automatic Encoding Selection:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingAutomaticEncodingSelection-f8b2w6e09tj0][Automatic Encoding Selection:2]]
+fn encode_page_with_dictionary<T>(
+    values: &[T],
+    file_offset: i64,
+    rle_options: &[bool],
+    dictionary: &mut DictionaryBuilder,
+    best: Encoding,
+) -> Result<Encoding>
+where
+    T: PhysicalValue,
+{
+    let dictionary_len = dictionary.len();
+    let dictionary_size = dictionary.encoded_values().len();
+    let mut best_dictionary_encoding: Option<Encoding> = None;
+    for use_rle in rle_options.iter().copied() {
+        let encoding =
+            match encode_values_with_dictionary(values, file_offset, use_rle, dictionary)? {
+                Some(e) => e,
+                None => break,
+            };
+        let is_better = best_dictionary_encoding
+            .as_ref()
+            .is_none_or(|b| encoding.encoded_values.len() < b.encoded_values.len());
+        if is_better {
+            best_dictionary_encoding = Some(encoding);
+        }
+    }
+    let added_size = dictionary.encoded_values().len() - dictionary_size;
+    match best_dictionary_encoding {
+        Some(e) if e.encoded_values.len() + added_size < best.encoded_values.len() => Ok(e),
+        _ => {
+            dictionary.truncate(dictionary_len);
+            Ok(best)
+        }
+    }
+}
+// Automatic Encoding Selection:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCompression-t6c3v9h10tj0][Compression:2]]
+fn compress_page(encoding: Encoding, compression: Compression) -> Result<Encoding> {
+    if compression == Compression::None {
+        return Ok(encoding);
+    }
+    let compressed = compression::compress(compression, &encoding.encoded_values)?;
+    if compressed.len() >= encoding.encoded_values.len() {
+        return Ok(encoding);
+    }
+    Ok(Encoding {
+        page_info: PageInfo {
+            compression,
+            compressed_size: compressed.len(),
+            ..encoding.page_info
+        },
+        encoded_values: compressed,
+    })
+}
+// Compression:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingNullableColumns-n5k2w8p13tj0][Nullable Columns:2]]
+fn add_validity(encoding: Encoding, validity: &[bool]) -> Encoding {
+    let null_count = validity.iter().filter(|is_valid| !**is_valid).count();
+    let mut encoded_values = Vec::new();
+    if null_count > 0 {
+        let bits: Vec<u128> = validity.iter().map(|is_valid| *is_valid as u128).collect();
+        encoded_values = bitpack::pack(&bits, 1);
+    }
+    encoded_values.extend_from_slice(&encoding.encoded_values);
+    Encoding {
+        page_info: PageInfo {
+            values_count: validity.len(),
+            null_count,
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            ..encoding.page_info
+        },
+        encoded_values,
+    }
+}
+// Nullable Columns:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingListColumns-c8r4j2w19tj0][List Columns:3]]
+// A row of a column holds either a single value or the values of a list.
+trait Row<T>: bincode::Encode {
+    // Appends the values of the row to values and returns how many there
+    // were.
+    fn append_to(self, values: &mut Vec<T>) -> usize;
+}
+
+impl<T: PhysicalValue> Row<T> for T {
+    fn append_to(self, values: &mut Vec<T>) -> usize {
+        values.push(self);
+        1
+    }
+}
+
+impl<T: PhysicalValue> Row<T> for Vec<T> {
+    fn append_to(self, values: &mut Vec<T>) -> usize {
+        let len = self.len();
+        values.extend(self);
+        len
+    }
+}
+
+fn add_lengths(encoding: Encoding, lengths: &[u64]) -> Result<Encoding> {
+    let (mut encoded_values, _) = encode_elements(lengths.iter().copied(), true)?;
+    encoded_values.extend_from_slice(&encoding.encoded_values);
+    Ok(Encoding {
+        page_info: PageInfo {
+            values_count: lengths.len(),
+            uncompressed_size: encoded_values.len(),
+            compressed_size: encoded_values.len(),
+            ..encoding.page_info
+        },
+        encoded_values,
+    })
+}
+// List Columns:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCategoricalColumns-s9k3d6w22tj0][Categorical Columns:3]]
+// Assigns codes to the labels of a categorical column.
+#[derive(Default)]
+struct CategoriesBuilder {
+    labels: Vec<String>,
+    codes: HashMap<String, u32>,
+}
+
+impl CategoriesBuilder {
+    fn codes_for<T: PhysicalValue>(&mut self, values: &[T]) -> Vec<u32> {
+        values
+            .iter()
+            .map(|v| {
+                let label = v.as_category().expect("categorical data type");
+                if let Some(code) = self.codes.get(label) {
+                    return *code;
+                }
+                let code = self.labels.len() as u32;
+                self.labels.push(label.to_string());
+                self.codes.insert(label.to_string(), code);
+                code
+            })
+            .collect()
+    }
+}
+// Categorical Columns:3 ends here
@@ -0,0 +1,57 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:17]]
+use crate::PhysicalValue;
+// Dependencies:17 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingFloatingPointEncoding-b6s2e9x14tj0][Floating Point Encoding:1]]
+pub fn total_eq<T: PhysicalValue>(a: &T, b: &T) -> bool {
+    match (a.to_float_bits(), b.to_float_bits()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+// Floating Point Encoding:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingFloatingPointEncoding-b6s2e9x14tj0][Floating Point Encoding:3]]
+pub struct TotalEq<'a, T>(pub &'a T);
+
+impl<T: PhysicalValue> PartialEq for TotalEq<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        total_eq(self.0, other.0)
+    }
+}
+
+impl<T: PhysicalValue> Eq for TotalEq<'_, T> {}
+
+impl<T: bincode::Encode> bincode::Encode for TotalEq<'_, T> {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+// Floating Point Encoding:3 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingFloatingPointEncoding-b6s2e9x14tj0][Floating Point Encoding:5]]
+pub fn split_streams(values: &[u64], byte_width: usize) -> Vec<u8> {
+    let mut streams = vec![0u8; values.len() * byte_width];
+    for (idx, bits) in values.iter().enumerate() {
+        for (byte, b) in bits.to_le_bytes()[..byte_width].iter().enumerate() {
+            streams[byte * values.len() + idx] = *b;
+        }
+    }
+    streams
+}
+
+pub fn join_streams(streams: &[u8], byte_width: usize, values_count: usize) -> Vec<u64> {
+    (0..values_count)
+        .map(|idx| {
+            let mut bytes = [0u8; 8];
+            for (byte, b) in bytes[..byte_width].iter_mut().enumerate() {
+                *b = streams[byte * values_count + idx];
+            }
+            u64::from_le_bytes(bytes)
+        })
+        .collect()
+}
+// Floating Point Encoding:5 ends here
@@ -1,45 +1,191 @@
 // [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:3]]
+mod binary;
+mod bitpack;
+mod boolean;
+mod compression;
 mod decode;
+mod delta;
+mod dictionary;
 mod encode;
+mod float;
+mod list;
+pub mod logical;
+mod map;
+mod nullable;
 pub mod rle;
+mod structs;
+mod value;
+mod xor;
 
+pub use decode::ColumnReader;
+pub use list::List;
+pub use map::Map;
+pub use nullable::ColumnItem;
+pub use structs::{field_path, ColumnStruct};
+pub use value::{Category, ColumnValue, FromColumnValue, PhysicalValue};
+
+#[cfg(test)]
+mod test_binary;
 #[cfg(test)]
 mod test_bincode;
 #[cfg(test)]
+mod test_bitpack;
+#[cfg(test)]
+mod test_boolean;
+#[cfg(test)]
+mod test_categorical;
+#[cfg(test)]
+mod test_compression;
+#[cfg(test)]
+mod test_delta;
+#[cfg(test)]
+mod test_dictionary;
+#[cfg(test)]
+mod test_float;
+#[cfg(test)]
 mod test_lib;
 #[cfg(test)]
+mod test_list;
+#[cfg(test)]
+mod test_logical;
+#[cfg(test)]
+mod test_map;
+#[cfg(test)]
+mod test_nullable;
+#[cfg(test)]
 mod test_rle;
+#[cfg(test)]
+mod test_structs;
+#[cfg(test)]
+mod test_value;
+#[cfg(test)]
+mod test_xor;
 
 use bincode::{Decode, Encode};
 use std::{
-    any::TypeId,
-    io::{Read, Seek, Write},
+    io::{Read, Seek, SeekFrom, Write},
+    ops::Range,
 };
 
-type Error = Box<dyn std::error::Error>;
 type Result<T> = std::result::Result<T, Error>;
-const BINCODE_DATA_CONFIG: bincode::config::Configuration = bincode::config::standard();
+const BINCODE_DATA_CONFIG: bincode::config::Configuration<
+    bincode::config::LittleEndian,
+    bincode::config::Varint,
+    bincode::config::Limit<MAX_DECODE_ALLOCATION>,
+> = bincode::config::standard().with_limit::<MAX_DECODE_ALLOCATION>();
 // Dependencies:3 ends here
 
 // [[file:../wills-columnar-format.org::#APIEncoding-w0g696o03tj0][Encoding:1]]
-pub fn encode_column<Iter, T, W>(data: Iter, w: &mut W, use_rle: bool) -> Result<Footer>
+pub fn encode_column<Iter, T, W>(data: Iter, w: &mut W) -> Result<Footer>
 where
     Iter: Iterator<Item = T>,
-    T: 'static + bincode::Encode + Eq,
+    T: ColumnItem,
     W: Write,
 {
-    encode::encode_column_impl(w, data, use_rle)
+    encode_column_with_options(data, w, EncodeOptions::default())
 }
 // Encoding:1 ends here
 
+// [[file:../wills-columnar-format.org::#APIEncoding-w0g696o03tj0][Encoding:2]]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub use_rle: bool,
+    pub use_dictionary: bool,
+    pub use_delta: bool,
+    pub use_bit_packing: bool,
+    pub use_byte_stream_split: bool,
+    pub use_xor: bool,
+    pub use_fixed_size_binary: bool,
+    pub max_dictionary_size: usize,
+    pub compression: Compression,
+    pub logical_type: Option<LogicalType>,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> EncodeOptions {
+        EncodeOptions {
+            use_rle: true,
+            use_dictionary: true,
+            use_delta: true,
+            use_bit_packing: true,
+            use_byte_stream_split: true,
+            use_xor: true,
+            use_fixed_size_binary: true,
+            max_dictionary_size: 1024 * 1024,
+            compression: Compression::None,
+            logical_type: None,
+        }
+    }
+}
+
+impl EncodeOptions {
+    pub fn plain() -> EncodeOptions {
+        EncodeOptions {
+            use_rle: false,
+            use_dictionary: false,
+            use_delta: false,
+            use_bit_packing: false,
+            use_byte_stream_split: false,
+            use_xor: false,
+            use_fixed_size_binary: false,
+            ..EncodeOptions::default()
+        }
+    }
+}
+
+pub fn encode_column_with_options<Iter, T, W>(
+    data: Iter,
+    w: &mut W,
+    options: EncodeOptions,
+) -> Result<Footer>
+where
+    Iter: Iterator<Item = T>,
+    T: ColumnItem,
+    W: Write,
+{
+    let mut table = TableWriter::new(w);
+    table.write_column_with_options("", data, options)?;
+    table.finish()
+}
+// Encoding:2 ends here
+
 // [[file:../wills-columnar-format.org::#APIDecoding-npg696o03tj0][Decoding:1]]
-pub fn decode_column<'a, T>(
-    r: impl 'a + Read + Seek,
-) -> Result<impl 'a + Iterator<Item = Result<rle::Values<T>>>>
+pub fn decode_column<T>(r: impl Read + Seek) -> Result<ColumnReader<impl Read + Seek, T>>
+where
+    T: 'static + ColumnItem + Clone,
+    T::Value: FromColumnValue,
+{
+    decode_column_with_options(r, DecodeOptions::default())
+}
+
+pub fn decode_column_with_options<T>(
+    r: impl Read + Seek,
+    options: DecodeOptions,
+) -> Result<ColumnReader<impl Read + Seek, T>>
+where
+    T: 'static + ColumnItem + Clone,
+    T::Value: FromColumnValue,
+{
+    let mut r = r;
+    let data_start = r.stream_position()?;
+    let footer = decode::decode_footer_impl(&mut r)?;
+    let column = match footer.columns.into_iter().next() {
+        Some(c) => c,
+        None => return Err(Error::ColumnNotFound { name: None }),
+    };
+    decode::decode_column_impl(r, data_start, column, options)
+}
+
+pub fn decode_range<T>(
+    r: impl Read + Seek,
+    range: Range<usize>,
+) -> Result<impl Iterator<Item = Result<rle::Values<T>>>>
 where
-    T: 'static + bincode::Decode,
+    T: 'static + ColumnItem + Clone,
+    T::Value: FromColumnValue,
 {
-    decode::decode_column_impl(r)
+    let reader = decode_column(r)?;
+    decode::decode_range_impl(reader, range)
 }
 
 pub fn decode_footer(r: impl Read + Seek) -> Result<Footer> {
@@ -48,14 +194,520 @@ pub fn decode_footer(r: impl Read + Seek) -> Result<Footer> {
 }
 // Decoding:1 ends here
 
+// [[file:../wills-columnar-format.org::#APITables-k2r8bqd07tj0][Tables:1]]
+pub struct TableWriter<W: Write> {
+    w: W,
+    file_offset: i64,
+    columns: Vec<ColumnInfo>,
+}
+
+impl<W: Write> TableWriter<W> {
+    pub fn new(w: W) -> TableWriter<W> {
+        TableWriter {
+            w,
+            file_offset: 0,
+            columns: Vec::new(),
+        }
+    }
+
+    pub fn write_column<Iter, T>(&mut self, name: &str, data: Iter) -> Result<&ColumnInfo>
+    where
+        Iter: Iterator<Item = T>,
+        T: ColumnItem,
+    {
+        self.write_column_with_options(name, data, EncodeOptions::default())
+    }
+
+    pub fn write_column_with_options<Iter, T>(
+        &mut self,
+        name: &str,
+        data: Iter,
+        options: EncodeOptions,
+    ) -> Result<&ColumnInfo>
+    where
+        Iter: Iterator<Item = T>,
+        T: ColumnItem,
+    {
+        if self.columns.iter().any(|c| c.name == name) {
+            return Err(Error::DuplicateColumn {
+                name: name.to_string(),
+            });
+        }
+        self.write_magic_bytes()?;
+        let column =
+            encode::encode_column_impl(&mut self.w, name, data, &mut self.file_offset, options)?;
+        self.columns.push(column);
+        Ok(self.columns.last().unwrap())
+    }
+
+    pub fn finish(mut self) -> Result<Footer> {
+        self.write_magic_bytes()?;
+        let mut w = self.w;
+        let footer = Footer {
+            version: FORMAT_VERSION,
+            columns: self.columns,
+        };
+        encode::encode_footer_impl(&mut w, &footer)?;
+        Ok(footer)
+    }
+}
+// Tables:1 ends here
+
+// [[file:../wills-columnar-format.org::#APITables-k2r8bqd07tj0][Tables:2]]
+pub struct TableReader<R: Read + Seek> {
+    r: R,
+    data_start: u64,
+    footer: Footer,
+}
+
+impl<R: Read + Seek> TableReader<R> {
+    pub fn new(r: R) -> Result<TableReader<R>> {
+        let mut r = r;
+        let data_start = r.stream_position()?;
+        let footer = decode::decode_footer_impl(&mut r)?;
+        Ok(TableReader {
+            r,
+            data_start,
+            footer,
+        })
+    }
+
+    pub fn footer(&self) -> &Footer {
+        &self.footer
+    }
+
+    pub fn column<T>(&mut self, name: &str) -> Result<ColumnReader<&mut R, T>>
+    where
+        T: 'static + ColumnItem + Clone,
+        T::Value: FromColumnValue,
+    {
+        self.column_with_options(name, DecodeOptions::default())
+    }
+
+    pub fn column_with_options<T>(
+        &mut self,
+        name: &str,
+        options: DecodeOptions,
+    ) -> Result<ColumnReader<&mut R, T>>
+    where
+        T: 'static + ColumnItem + Clone,
+        T::Value: FromColumnValue,
+    {
+        let column = decode::find_column(&self.footer, name)?.clone();
+        decode::decode_column_impl(&mut self.r, self.data_start, column, options)
+    }
+}
+// Tables:2 ends here
+
+// [[file:../wills-columnar-format.org::#APIStructColumns-h2v6c1x20tj0][Struct Columns:2]]
+impl<W: Write> TableWriter<W> {
+    pub fn write_struct<Iter, T>(&mut self, name: &str, data: Iter) -> Result<&[ColumnInfo]>
+    where
+        Iter: Iterator<Item = T>,
+        T: ColumnStruct,
+    {
+        self.write_struct_with_options(name, data, EncodeOptions::default())
+    }
+
+    pub fn write_struct_with_options<Iter, T>(
+        &mut self,
+        name: &str,
+        data: Iter,
+        options: EncodeOptions,
+    ) -> Result<&[ColumnInfo]>
+    where
+        Iter: Iterator<Item = T>,
+        T: ColumnStruct,
+    {
+        if options.logical_type.is_some() {
+            return Err(Error::StructLogicalType {
+                name: name.to_string(),
+            });
+        }
+        let rows: Vec<T> = data.collect();
+        let start = self.columns.len();
+        T::write_fields(self, name, &rows, &options)?;
+        Ok(&self.columns[start..])
+    }
+}
+
+impl<R: Read + Seek> TableReader<R> {
+    pub fn read_struct<T: ColumnStruct>(&mut self, name: &str) -> Result<Vec<T>> {
+        T::read_fields(self, name)
+    }
+
+    pub fn read_rows<T>(&mut self, name: &str) -> Result<Vec<T>>
+    where
+        T: 'static + ColumnItem + Clone,
+        T::Value: FromColumnValue,
+    {
+        let mut rows = Vec::new();
+        for values in self.column::<T>(name)? {
+            let values = values?;
+            rows.extend(std::iter::repeat_n(
+                values.value,
+                values.run_length as usize,
+            ));
+        }
+        Ok(rows)
+    }
+}
+// Struct Columns:2 ends here
+
+// [[file:../wills-columnar-format.org::#APISchemaEvolution-r8w3k5j16tj0][Schema Evolution:1]]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub allow_widening: bool,
+}
+// Schema Evolution:1 ends here
+
+// [[file:../wills-columnar-format.org::#APIErrors-g4h8s2m12tj0][Errors:1]]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    Io(std::io::Error),
+    BincodeEncode(bincode::error::EncodeError),
+    BincodeDecode(bincode::error::DecodeError),
+    // The column was decoded as a type that does not match its data type.
+    DataTypeMismatch {
+        expected: DataType,
+        actual: &'static str,
+    },
+    ColumnNotFound {
+        name: Option<String>,
+    },
+    DuplicateColumn {
+        name: String,
+    },
+    CorruptFooter {
+        reason: String,
+    },
+    // The bytes at file_offset do not match the checksum they were written
+    // with.
+    ChecksumMismatch {
+        file_offset: i64,
+        expected: u32,
+        actual: u32,
+    },
+    // The file does not start or end with MAGIC_BYTES and is not a version 0
+    // file either.
+    InvalidMagicBytes {
+        found: [u8; 4],
+    },
+    // The footer was written by a version of the format that is not supported.
+    UnsupportedVersion {
+        version: u16,
+    },
+    // The values decoded from a page do not match the values_count of the page.
+    PageCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidDictionaryCode {
+        code: u64,
+        dictionary_size: usize,
+    },
+    IntegerOutOfRange {
+        value: i128,
+        actual: &'static str,
+    },
+    RowOutOfRange {
+        row: usize,
+        rows_count: usize,
+    },
+    // The logical type can not be stored on top of the data type.
+    LogicalTypeMismatch {
+        logical_type: LogicalType,
+        data_type: DataType,
+    },
+    // A value can not be stored as the logical type, like a UUID that is not
+    // 16 bytes.
+    InvalidLogicalValue {
+        logical_type: LogicalType,
+        reason: String,
+    },
+    // The column was decoded as a type with a different logical type.
+    UnexpectedLogicalType {
+        logical_type: Option<LogicalType>,
+        actual: &'static str,
+    },
+    // An integer column was decoded into an integer of a different size.
+    IntegerWidthMismatch {
+        width: u8,
+        actual: &'static str,
+    },
+    // A list column was decoded into a type that is not a List, or the other
+    // way around.
+    RepeatedMismatch {
+        repeated: bool,
+        actual: &'static str,
+    },
+    // The child columns of a struct have a different number of rows.
+    StructRowsMismatch {
+        name: String,
+        expected: usize,
+        actual: usize,
+    },
+    // A logical type was set for a struct, which would apply it to every field.
+    StructLogicalType {
+        name: String,
+    },
+    // A row of a map has a different number of keys and values.
+    MapLengthMismatch {
+        row: usize,
+        keys: usize,
+        values: usize,
+    },
+    InvalidCategoryCode {
+        code: u32,
+        categories_count: usize,
+    },
+    // A categorical column holds a label that is not a variant of the enum.
+    UnknownCategory {
+        label: String,
+        actual: &'static str,
+    },
+    // A page holds more than MAX_PAGE_VALUES values. The elements of a list
+    // column count towards the page that holds their rows.
+    PageTooLarge {
+        values_count: usize,
+    },
+    // The bytes of a page, before compression, are more than
+    // MAX_DECODE_ALLOCATION.
+    PageSizeTooLarge {
+        size: usize,
+    },
+    // A column with nulls was decoded into a type that is not an Option.
+    UnexpectedNulls {
+        null_count: usize,
+        actual: &'static str,
+    },
+    Codec {
+        compression: Compression,
+        reason: String,
+    },
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::BincodeEncode(err) => Some(err),
+            Error::BincodeDecode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::BincodeEncode(err) => write!(f, "{}", err),
+            Error::BincodeDecode(err) => write!(f, "{}", err),
+            Error::DataTypeMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Expected format of type {:?} but got {}",
+                    expected, actual
+                )
+            }
+            Error::ColumnNotFound { name: Some(name) } => write!(f, "Column {:?} not found", name),
+            Error::ColumnNotFound { name: None } => write!(f, "File does not contain any columns"),
+            Error::DuplicateColumn { name } => write!(f, "Column {:?} was already written", name),
+            Error::CorruptFooter { reason } => write!(f, "Footer is corrupt: {}", reason),
+            Error::PageCountMismatch { expected, actual } => write!(
+                f,
+                "Page contains {} values but expected {} values",
+                actual, expected
+            ),
+            Error::InvalidDictionaryCode {
+                code,
+                dictionary_size,
+            } => write!(
+                f,
+                "Dictionary code {} is out of range for dictionary of size {}",
+                code, dictionary_size
+            ),
+            Error::IntegerOutOfRange { value, actual } => {
+                write!(f, "Value {} does not fit into {}", value, actual)
+            }
+            Error::RowOutOfRange { row, rows_count } => write!(
+                f,
+                "Row {} is out of range for a column with {} rows",
+                row, rows_count
+            ),
+            Error::LogicalTypeMismatch {
+                logical_type,
+                data_type,
+            } => write!(
+                f,
+                "Logical type {:?} can not be stored as {:?}",
+                logical_type, data_type
+            ),
+            Error::InvalidLogicalValue {
+                logical_type,
+                reason,
+            } => write!(
+                f,
+                "Invalid value for logical type {:?}: {}",
+                logical_type, reason
+            ),
+            Error::UnexpectedLogicalType {
+                logical_type,
+                actual,
+            } => write!(
+                f,
+                "Column with logical type {:?} can not be decoded into {}",
+                logical_type, actual
+            ),
+            Error::IntegerWidthMismatch { width, actual } => write!(
+                f,
+                "Column contains {} byte integers which can not be decoded into {}",
+                width, actual
+            ),
+            Error::RepeatedMismatch { repeated, actual } => write!(
+                f,
+                "Column {} a list and can not be decoded into {}",
+                if *repeated { "is" } else { "is not" },
+                actual
+            ),
+            Error::StructRowsMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Column {} has {} rows but the struct has {} rows",
+                name, actual, expected
+            ),
+            Error::StructLogicalType { name } => write!(
+                f,
+                "Struct {} can not have a logical type since it would apply to every field",
+                name
+            ),
+            Error::MapLengthMismatch { row, keys, values } => write!(
+                f,
+                "Map at row {} has {} keys but {} values",
+                row, keys, values
+            ),
+            Error::InvalidCategoryCode {
+                code,
+                categories_count,
+            } => write!(
+                f,
+                "Category code {} is out of range for {} categories",
+                code, categories_count
+            ),
+            Error::UnknownCategory { label, actual } => {
+                write!(f, "Category {} is not a variant of {}", label, actual)
+            }
+            Error::ChecksumMismatch {
+                file_offset,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum of the bytes at offset {} is {:#010x} but {:#010x} was expected",
+                file_offset, actual, expected
+            ),
+            Error::InvalidMagicBytes { found } => write!(
+                f,
+                "File is not in Will's Columnar Format, found magic bytes {:?}",
+                found
+            ),
+            Error::UnsupportedVersion { version } => write!(
+                f,
+                "Format version {} is not supported, the latest is {}",
+                version, FORMAT_VERSION
+            ),
+            Error::PageTooLarge { values_count } => write!(
+                f,
+                "Page holds {} values which is more than the maximum of {}",
+                values_count, MAX_PAGE_VALUES
+            ),
+            Error::PageSizeTooLarge { size } => write!(
+                f,
+                "Page holds {} bytes which is more than the maximum of {}",
+                size, MAX_DECODE_ALLOCATION
+            ),
+            Error::UnexpectedNulls { null_count, actual } => write!(
+                f,
+                "Column contains {} nulls which can not be decoded into {}",
+                null_count, actual
+            ),
+            Error::Codec {
+                compression,
+                reason,
+            } => write!(f, "{:?} codec failed: {}", compression, reason),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<bincode::error::EncodeError> for Error {
+    fn from(err: bincode::error::EncodeError) -> Error {
+        Error::BincodeEncode(err)
+    }
+}
+
+impl From<bincode::error::DecodeError> for Error {
+    fn from(err: bincode::error::DecodeError) -> Error {
+        Error::BincodeDecode(err)
+    }
+}
+// Errors:1 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFormatOverview-j3k696o03tj0][Format Overview:2]]
+pub const MAGIC_BYTES: [u8; 4] = *b"WCOL";
+pub const FORMAT_VERSION: u16 = 1;
+
+impl<W: Write> TableWriter<W> {
+    // Writes the leading magic bytes if nothing has been written yet.
+    fn write_magic_bytes(&mut self) -> Result<()> {
+        if self.file_offset == 0 {
+            self.w.write_all(&MAGIC_BYTES)?;
+            self.file_offset = MAGIC_BYTES.len() as i64;
+        }
+        Ok(())
+    }
+}
+// Format Overview:2 ends here
+
 // [[file:../wills-columnar-format.org::#FormatSpecificationFileFooter-nn404df05tj0][File Footer:2]]
 #[derive(Encode, Decode, PartialEq, Eq, Clone, Debug)]
 pub struct Footer {
+    pub version: u16,
+    pub columns: Vec<ColumnInfo>,
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug)]
+pub struct ColumnInfo {
+    pub name: String,
     pub data_type: DataType,
-    pub use_rle: bool,
+    pub integer_width: Option<u8>,
+    pub logical_type: Option<LogicalType>,
+    pub nullable: bool,
+    pub repeated: bool,
+    pub null_count: usize,
+    pub dictionary: Option<DictionaryInfo>,
+    pub categories: Vec<String>,
     pub pages: Vec<PageInfo>,
 }
 
+#[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, Debug)]
+pub struct DictionaryInfo {
+    pub file_offset: i64,
+    pub values_count: usize,
+    pub size: usize,
+    pub checksum: u32,
+}
+
 #[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, Debug)]
 pub enum DataType {
     UnsignedByte = 0,
@@ -63,53 +715,171 @@ pub enum DataType {
     UnsignedInteger = 2,
     SignedInteger = 3,
     String = 4,
+    Float = 5,
+    Double = 6,
+    Boolean = 7,
+    Binary = 8,
+    Categorical = 9,
 }
 
 #[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, Debug)]
 pub struct PageInfo {
     pub file_offset: i64,
     pub values_count: usize,
+    pub null_count: usize,
     pub encoded_values_count: usize,
+    pub encoding: PageEncoding,
+    pub use_rle: bool,
+    pub compression: Compression,
+    pub uncompressed_size: usize,
+    pub compressed_size: usize,
+    pub checksum: u32,
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum PageEncoding {
+    Plain,
+    Dictionary,
+    Delta,
+    DeltaOfDelta,
+    BitPacked { min: i128, bit_width: u8 },
+    ByteStreamSplit,
+    Xor,
+    PackedBooleans,
+    FixedSizeBinary { size: usize },
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+    Lz4,
+    Snappy,
+    Deflate,
 }
 // File Footer:2 ends here
 
 // [[file:../wills-columnar-format.org::#FormatSpecificationFileFooter-nn404df05tj0][File Footer:3]]
 impl DataType {
-    const ALL_DATA_TYPE: [DataType; 5] = [
-        DataType::UnsignedByte,
-        DataType::SignedByte,
-        DataType::UnsignedInteger,
-        DataType::SignedInteger,
-        DataType::String,
-    ];
-
-    fn from_type<T: 'static>() -> Option<DataType> {
-        DataType::ALL_DATA_TYPE
-            .into_iter()
-            .find(|dt| dt.is_supported::<T>())
-    }
-
-    fn is_supported<T: 'static>(&self) -> bool {
-        let type_id = TypeId::of::<T>();
+    fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            DataType::UnsignedByte
+                | DataType::SignedByte
+                | DataType::UnsignedInteger
+                | DataType::SignedInteger
+        )
+    }
+
+    fn is_signed_integer(&self) -> bool {
+        matches!(self, DataType::SignedByte | DataType::SignedInteger)
+    }
+
+    fn is_float(&self) -> bool {
+        matches!(self, DataType::Float | DataType::Double)
+    }
+}
+// File Footer:3 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationChecksums-v3p8n6j24tj0][Checksums:3]]
+pub fn verify_file(r: impl Read + Seek) -> Result<()> {
+    let mut r = r;
+    let data_start = r.stream_position()?;
+    let footer = decode::decode_footer_impl(&mut r)?;
+    for column in footer.columns.iter() {
+        for page in column.pages.iter() {
+            r.seek(SeekFrom::Start(data_start + page.file_offset as u64))?;
+            decode::read_page_bytes(&mut r, page)?;
+        }
+        if let Some(dictionary) = &column.dictionary {
+            r.seek(SeekFrom::Start(data_start + dictionary.file_offset as u64))?;
+            decode::read_dictionary_bytes(&mut r, dictionary)?;
+        }
+    }
+    Ok(())
+}
+// Checksums:3 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFooterValidation-w5k2r8p25tj0][Footer Validation:1]]
+pub const MAX_DECODE_ALLOCATION: usize = 1 << 28;
+pub const MAX_PAGE_VALUES: usize = 1 << 20;
+// Footer Validation:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingLogicalTypes-u6n1b8s17tj0][Logical Types:1]]
+#[derive(Encode, Decode, PartialEq, Eq, Clone, Debug)]
+pub enum LogicalType {
+    Timestamp {
+        unit: TimeUnit,
+        timezone: Option<String>,
+    },
+    Date,
+    Time {
+        unit: TimeUnit,
+    },
+    Duration {
+        unit: TimeUnit,
+    },
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
+    Uuid,
+}
+
+#[derive(Encode, Decode, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum TimeUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl LogicalType {
+    fn is_supported(&self, data_type: DataType, integer_width: Option<u8>) -> bool {
+        let is_signed = data_type.is_signed_integer();
         match self {
-            DataType::UnsignedByte => TypeId::of::<u8>() == type_id,
-            DataType::SignedByte => TypeId::of::<i8>() == type_id,
-            DataType::UnsignedInteger => [
-                TypeId::of::<u16>(),
-                TypeId::of::<u32>(),
-                TypeId::of::<u64>(),
-            ]
-            .contains(&type_id),
-            DataType::SignedInteger => [
-                TypeId::of::<i16>(),
-                TypeId::of::<i32>(),
-                TypeId::of::<i64>(),
-            ]
-            .contains(&type_id),
-            DataType::String => {
-                [TypeId::of::<String>(), TypeId::of::<&'static str>()].contains(&type_id)
+            LogicalType::Timestamp { .. }
+            | LogicalType::Time { .. }
+            | LogicalType::Duration { .. } => is_signed && integer_width == Some(8),
+            LogicalType::Date => is_signed && integer_width == Some(4),
+            LogicalType::Decimal { precision, scale } => {
+                let max_precision = match integer_width {
+                    Some(4) => 9,
+                    Some(8) => 18,
+                    Some(16) => 38,
+                    _ => 0,
+                };
+                is_signed && (1..=max_precision).contains(precision) && scale <= precision
+            }
+            LogicalType::Uuid => {
+                (data_type == DataType::UnsignedInteger && integer_width == Some(16))
+                    || data_type == DataType::Binary
             }
         }
     }
+
+    fn validate_value<T: PhysicalValue>(&self, value: &T) -> Result<()> {
+        match (self, value.as_bytes()) {
+            (LogicalType::Uuid, Some(bytes)) if bytes.len() != 16 => {
+                Err(Error::InvalidLogicalValue {
+                    logical_type: self.clone(),
+                    reason: format!("UUIDs are 16 bytes but a value has {} bytes", bytes.len()),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
 }
-// File Footer:3 ends here
+
+impl TimeUnit {
+    fn nanos(&self) -> i128 {
+        match self {
+            TimeUnit::Seconds => 1_000_000_000,
+            TimeUnit::Milliseconds => 1_000_000,
+            TimeUnit::Microseconds => 1_000,
+            TimeUnit::Nanoseconds => 1,
+        }
+    }
+}
+// Logical Types:1 ends here
@@ -0,0 +1,50 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:27]]
+use crate::{ColumnItem, ColumnValue};
+// Dependencies:27 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingListColumns-c8r4j2w19tj0][List Columns:1]]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for List<T> {
+    fn from(values: Vec<T>) -> List<T> {
+        List(values)
+    }
+}
+
+impl<T: ColumnValue> ColumnItem for List<T> {
+    type Value = T;
+    const NULLABLE: bool = false;
+    const REPEATED: bool = true;
+
+    fn values(&self) -> Option<&[T]> {
+        Some(&self.0)
+    }
+
+    fn from_value(_value: Option<T>) -> Option<List<T>> {
+        None
+    }
+
+    fn from_values(values: Option<Vec<T>>) -> Option<List<T>> {
+        values.map(List)
+    }
+}
+
+impl<T: ColumnValue> ColumnItem for Option<List<T>> {
+    type Value = T;
+    const NULLABLE: bool = true;
+    const REPEATED: bool = true;
+
+    fn values(&self) -> Option<&[T]> {
+        self.as_ref().map(|list| list.0.as_slice())
+    }
+
+    fn from_value(_value: Option<T>) -> Option<Option<List<T>>> {
+        None
+    }
+
+    fn from_values(values: Option<Vec<T>>) -> Option<Option<List<T>>> {
+        Some(values.map(List))
+    }
+}
+// List Columns:1 ends here
@@ -0,0 +1,92 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:22]]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::TimeUnit;
+// Dependencies:22 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingLogicalTypes-u6n1b8s17tj0][Logical Types:2]]
+pub fn timestamp_from_system_time(time: SystemTime, unit: TimeUnit) -> Option<i64> {
+    let nanos = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_nanos() as i128,
+        Err(err) => -(err.duration().as_nanos() as i128),
+    };
+    i64::try_from(nanos.div_euclid(unit.nanos())).ok()
+}
+
+pub fn system_time_from_timestamp(value: i64, unit: TimeUnit) -> Option<SystemTime> {
+    let (seconds, nanos) = split_nanos(value as i128 * unit.nanos());
+    let time = if seconds >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(seconds as u64))?
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(seconds.unsigned_abs() as u64))?
+    };
+    time.checked_add(Duration::from_nanos(nanos as u64))
+}
+
+pub fn duration_value(duration: Duration, unit: TimeUnit) -> Option<i64> {
+    i64::try_from(duration.as_nanos() as i128 / unit.nanos()).ok()
+}
+
+pub fn duration_from_value(value: i64, unit: TimeUnit) -> Option<Duration> {
+    let (seconds, nanos) = split_nanos(value as i128 * unit.nanos());
+    Some(Duration::new(u64::try_from(seconds).ok()?, nanos as u32))
+}
+
+// Splits nanos into whole seconds and the remaining nanos, which are always
+// positive.
+fn split_nanos(nanos: i128) -> (i128, i128) {
+    (
+        nanos.div_euclid(1_000_000_000),
+        nanos.rem_euclid(1_000_000_000),
+    )
+}
+// Logical Types:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingLogicalTypes-u6n1b8s17tj0][Logical Types:3]]
+#[cfg(feature = "chrono")]
+pub fn timestamp_from_date_time<Tz: chrono::TimeZone>(
+    time: &chrono::DateTime<Tz>,
+    unit: TimeUnit,
+) -> Option<i64> {
+    let nanos = time.timestamp() as i128 * 1_000_000_000 + time.timestamp_subsec_nanos() as i128;
+    i64::try_from(nanos.div_euclid(unit.nanos())).ok()
+}
+
+#[cfg(feature = "chrono")]
+pub fn date_time_from_timestamp(
+    value: i64,
+    unit: TimeUnit,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let (seconds, nanos) = split_nanos(value as i128 * unit.nanos());
+    chrono::DateTime::from_timestamp(i64::try_from(seconds).ok()?, nanos as u32)
+}
+
+#[cfg(feature = "chrono")]
+pub fn date_from_naive_date(date: chrono::NaiveDate) -> i32 {
+    // Every NaiveDate is within i32::MAX days of the epoch.
+    date.signed_duration_since(chrono::NaiveDate::default())
+        .num_days() as i32
+}
+
+#[cfg(feature = "chrono")]
+pub fn naive_date_from_date(value: i32) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::default().checked_add_signed(chrono::TimeDelta::days(value as i64))
+}
+
+#[cfg(feature = "chrono")]
+pub fn time_from_naive_time(time: chrono::NaiveTime, unit: TimeUnit) -> i64 {
+    use chrono::Timelike;
+    let nanos =
+        time.num_seconds_from_midnight() as i128 * 1_000_000_000 + time.nanosecond() as i128;
+    (nanos / unit.nanos()) as i64
+}
+
+#[cfg(feature = "chrono")]
+pub fn naive_time_from_time(value: i64, unit: TimeUnit) -> Option<chrono::NaiveTime> {
+    let (seconds, nanos) = split_nanos(value as i128 * unit.nanos());
+    chrono::NaiveTime::from_num_seconds_from_midnight_opt(
+        u32::try_from(seconds).ok()?,
+        nanos as u32,
+    )
+}
+// Logical Types:3 ends here
@@ -0,0 +1,98 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:29]]
+use crate::{
+    field_path, ColumnStruct, EncodeOptions, Error, FromColumnValue, List, Result, TableReader,
+    TableWriter,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::io::{Read, Seek, Write};
+// Dependencies:29 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingMapColumns-q4f7t2b21tj0][Map Columns:1]]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Map<K, V>(pub Vec<(K, V)>);
+
+impl<K, V> FromIterator<(K, V)> for Map<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Map<K, V> {
+        Map(iter.into_iter().collect())
+    }
+}
+
+impl<K, V> From<HashMap<K, V>> for Map<K, V> {
+    fn from(map: HashMap<K, V>) -> Map<K, V> {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for Map<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Map<K, V> {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Eq + Hash, V> From<Map<K, V>> for HashMap<K, V> {
+    fn from(map: Map<K, V>) -> HashMap<K, V> {
+        map.0.into_iter().collect()
+    }
+}
+
+impl<K: Ord, V> From<Map<K, V>> for BTreeMap<K, V> {
+    fn from(map: Map<K, V>) -> BTreeMap<K, V> {
+        map.0.into_iter().collect()
+    }
+}
+// Map Columns:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingMapColumns-q4f7t2b21tj0][Map Columns:2]]
+impl<K: FromColumnValue, V: FromColumnValue> ColumnStruct for Map<K, V> {
+    fn write_fields<W: Write>(
+        table: &mut TableWriter<W>,
+        name: &str,
+        rows: &[Map<K, V>],
+        options: &EncodeOptions,
+    ) -> Result<()> {
+        table.write_column_with_options(
+            &field_path(name, "keys"),
+            rows.iter()
+                .map(|map| List(map.0.iter().map(|(k, _)| k.clone()).collect())),
+            options.clone(),
+        )?;
+        table.write_column_with_options(
+            &field_path(name, "values"),
+            rows.iter()
+                .map(|map| List(map.0.iter().map(|(_, v)| v.clone()).collect())),
+            options.clone(),
+        )?;
+        Ok(())
+    }
+
+    fn read_fields<R: Read + Seek>(
+        table: &mut TableReader<R>,
+        name: &str,
+    ) -> Result<Vec<Map<K, V>>> {
+        let keys: Vec<List<K>> = table.read_rows(&field_path(name, "keys"))?;
+        let values: Vec<List<V>> = table.read_rows(&field_path(name, "values"))?;
+        if keys.len() != values.len() {
+            return Err(Error::StructRowsMismatch {
+                name: field_path(name, "values"),
+                expected: keys.len(),
+                actual: values.len(),
+            });
+        }
+        keys.into_iter()
+            .zip(values)
+            .enumerate()
+            .map(|(row, (keys, values))| {
+                if keys.0.len() != values.0.len() {
+                    return Err(Error::MapLengthMismatch {
+                        row,
+                        keys: keys.0.len(),
+                        values: values.0.len(),
+                    });
+                }
+                Ok(keys.0.into_iter().zip(values.0).collect())
+            })
+            .collect()
+    }
+}
+// Map Columns:2 ends here
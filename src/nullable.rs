@@ -0,0 +1,62 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:16]]
+use crate::ColumnValue;
+// Dependencies:16 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingNullableColumns-n5k2w8p13tj0][Nullable Columns:1]]
+// The items of a column. Columns of a ColumnValue are not nullable while
+// columns of an Option of a ColumnValue are. Columns of a List hold several
+// values per item.
+pub trait ColumnItem: Sized {
+    type Value: ColumnValue;
+    const NULLABLE: bool;
+    const REPEATED: bool;
+
+    // Returns the values of the item, or None if it is null. Items that are not
+    // repeated have exactly one value.
+    fn values(&self) -> Option<&[Self::Value]>;
+
+    // Returns None if value is null but Self is not nullable, or if Self is
+    // repeated.
+    fn from_value(value: Option<Self::Value>) -> Option<Self>;
+
+    // Returns None if values is null but Self is not nullable, or if Self is
+    // not repeated.
+    fn from_values(values: Option<Vec<Self::Value>>) -> Option<Self>;
+}
+
+impl<T: ColumnValue> ColumnItem for T {
+    type Value = T;
+    const NULLABLE: bool = false;
+    const REPEATED: bool = false;
+
+    fn values(&self) -> Option<&[T]> {
+        Some(std::slice::from_ref(self))
+    }
+
+    fn from_value(value: Option<T>) -> Option<T> {
+        value
+    }
+
+    fn from_values(_values: Option<Vec<T>>) -> Option<T> {
+        None
+    }
+}
+
+impl<T: ColumnValue> ColumnItem for Option<T> {
+    type Value = T;
+    const NULLABLE: bool = true;
+    const REPEATED: bool = false;
+
+    fn values(&self) -> Option<&[T]> {
+        self.as_ref().map(std::slice::from_ref)
+    }
+
+    fn from_value(value: Option<T>) -> Option<Option<T>> {
+        Some(value)
+    }
+
+    fn from_values(_values: Option<Vec<T>>) -> Option<Option<T>> {
+        None
+    }
+}
+// Nullable Columns:1 ends here
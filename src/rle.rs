@@ -1,6 +1,7 @@
 // [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:4]]
 use bincode::{Decode, Encode};
 use itertools::Itertools;
+use std::iter::Peekable;
 // Dependencies:4 ends here
 
 // [[file:../wills-columnar-format.org::#DataEncodingRunLengthEncoding-0vm696o03tj0][Run Length Encoding:2]]
@@ -28,24 +29,28 @@ impl<T> Values<T> {
 pub fn encode_iter<'a, T: 'a + bincode::Encode + Eq>(
     data: impl 'a + Iterator<Item = T>,
 ) -> impl 'a + Iterator<Item = Values<T>> {
-    data.peekable().batching(move |iter| -> Option<Values<T>> {
-        let element = iter.next()?;
-        let mut run_length = 1;
-        while iter.next_if_eq(&element).is_some() {
-            run_length += 1;
-        }
-        Some(Values {
-            value: element,
-            run_length,
-        })
-    })
+    data.peekable().batching(encode_next)
 }
 // Run Length Encoding:3 ends here
 
 // [[file:../wills-columnar-format.org::#DataEncodingRunLengthEncoding-0vm696o03tj0][Run Length Encoding:4]]
+pub fn encode_next<T: Eq>(iter: &mut Peekable<impl Iterator<Item = T>>) -> Option<Values<T>> {
+    let element = iter.next()?;
+    let mut run_length = 1;
+    while iter.next_if_eq(&element).is_some() {
+        run_length += 1;
+    }
+    Some(Values {
+        value: element,
+        run_length,
+    })
+}
+// Run Length Encoding:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingRunLengthEncoding-0vm696o03tj0][Run Length Encoding:5]]
 impl<T> Values<T> {
     pub fn repeated(&self) -> impl '_ + Iterator<Item = &'_ T> {
-        std::iter::repeat(&self.value).take(self.run_length as usize)
+        std::iter::repeat_n(&self.value, self.run_length as usize)
     }
 }
-// Run Length Encoding:4 ends here
+// Run Length Encoding:5 ends here
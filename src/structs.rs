@@ -0,0 +1,77 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:31]]
+use crate::{EncodeOptions, Result, TableReader, TableWriter};
+use std::io::{Read, Seek, Write};
+// Dependencies:31 ends here
+
+// [[file:../wills-columnar-format.org::#APIStructColumns-h2v6c1x20tj0][Struct Columns:1]]
+pub trait ColumnStruct: Sized {
+    // Writes each field of rows as a child column of name.
+    fn write_fields<W: Write>(
+        table: &mut TableWriter<W>,
+        name: &str,
+        rows: &[Self],
+        options: &EncodeOptions,
+    ) -> Result<()>;
+
+    // Reads every row from the child columns of name.
+    fn read_fields<R: Read + Seek>(table: &mut TableReader<R>, name: &str) -> Result<Vec<Self>>;
+}
+
+// Returns the name of the child column that stores field.
+pub fn field_path(name: &str, field: &str) -> String {
+    if name.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", name, field)
+    }
+}
+
+#[macro_export]
+macro_rules! column_struct {
+    ($name:ident { $($field:ident),+ $(,)? }) => {
+        impl $crate::ColumnStruct for $name {
+            fn write_fields<W: std::io::Write>(
+                table: &mut $crate::TableWriter<W>,
+                name: &str,
+                rows: &[$name],
+                options: &$crate::EncodeOptions,
+            ) -> std::result::Result<(), $crate::Error> {
+                $(
+                    table.write_column_with_options(
+                        &$crate::field_path(name, stringify!($field)),
+                        rows.iter().map(|row| row.$field.clone()),
+                        options.clone(),
+                    )?;
+                )+
+                Ok(())
+            }
+
+            fn read_fields<R: std::io::Read + std::io::Seek>(
+                table: &mut $crate::TableReader<R>,
+                name: &str,
+            ) -> std::result::Result<Vec<$name>, $crate::Error> {
+                $(
+                    let mut $field = table
+                        .read_rows(&$crate::field_path(name, stringify!($field)))?
+                        .into_iter();
+                )+
+                let rows_count = [$($field.len()),+].into_iter().max().unwrap_or(0);
+                $(
+                    if $field.len() != rows_count {
+                        return Err($crate::Error::StructRowsMismatch {
+                            name: $crate::field_path(name, stringify!($field)),
+                            expected: rows_count,
+                            actual: $field.len(),
+                        });
+                    }
+                )+
+                Ok((0..rows_count)
+                    .map(|_| $name {
+                        $($field: $field.next().unwrap()),+
+                    })
+                    .collect())
+            }
+        }
+    };
+}
+// Struct Columns:1 ends here
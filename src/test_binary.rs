@@ -0,0 +1,88 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:24]]
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:24 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBinaryEncodingTests-y8g2p4n16tj0][Tests:1]]
+#[test]
+fn test_fixed_size_values_are_stored_without_lengths() {
+    let digests: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 32]).collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(digests.clone().into_iter(), &mut encoded_data).unwrap();
+    let page = footer.columns[0].pages[0];
+    assert_eq!(page.encoding, PageEncoding::FixedSizeBinary { size: 32 });
+    assert_eq!(page.compressed_size, 4 * 32);
+    assert_equal(
+        decode_column::<Vec<u8>>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        digests.into_iter().map(rle::Values::single),
+    );
+}
+
+#[test]
+fn test_values_of_different_sizes_store_lengths() {
+    let data: Vec<Vec<u8>> = vec![vec![1, 2, 3], vec![], vec![4]];
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    let page = footer.columns[0].pages[0];
+    assert_eq!(page.encoding, PageEncoding::Plain);
+    assert_eq!(
+        page.compressed_size,
+        [
+            1, // varint length
+            3, // bytes
+            1, // varint length
+            1, // varint length
+            1, // bytes
+        ]
+        .iter()
+        .sum()
+    );
+    assert_equal(
+        decode_column::<Vec<u8>>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.into_iter().map(rle::Values::single),
+    );
+}
+
+#[test]
+fn test_slices_and_boxes_are_interchangeable() {
+    let data: [&'static [u8]; 3] = [b"abc", b"abc", b"de"];
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(footer.columns[0].data_type, DataType::Binary);
+    assert_equal(
+        decode_column::<Box<[u8]>>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        [
+            rle::Values {
+                value: Box::from(&b"abc"[..]),
+                run_length: 2,
+            },
+            rle::Values::single(Box::from(&b"de"[..])),
+        ],
+    );
+}
+
+#[test]
+fn test_nullable_binary() {
+    let data = [Some(vec![1u8; 32]), None, Some(vec![2u8; 32])];
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(
+        footer.columns[0].pages[0].encoding,
+        PageEncoding::FixedSizeBinary { size: 32 }
+    );
+    assert_equal(
+        decode_column::<Option<Vec<u8>>>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        data,
+    );
+}
+// Tests:1 ends here
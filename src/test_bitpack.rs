@@ -0,0 +1,95 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:11]]
+use crate::bitpack::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:11 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBitPackingTests-m2j6y4d08tj0][Tests:1]]
+#[test]
+fn test_bit_width() {
+    assert_eq!(bit_width(0), 0);
+    assert_eq!(bit_width(1), 1);
+    assert_eq!(bit_width(100), 7);
+    assert_eq!(bit_width(127), 7);
+    assert_eq!(bit_width(128), 8);
+    assert_eq!(bit_width(u64::MAX as u128), 64);
+}
+
+#[test]
+fn test_pack_unpack_round_trip() {
+    let offsets = [3u128, 0, 100, 42, 127];
+    let packed = pack(&offsets, 7);
+    // 5 values of 7 bits take up 35 bits.
+    assert_eq!(packed.len(), 5);
+    assert_eq!(packed[0], 0b0000_0011);
+    assert_equal(unpack(&packed, 7, offsets.len()), offsets);
+
+    let offsets = [u64::MAX as u128, 0, 1];
+    assert_equal(unpack(&pack(&offsets, 64), 64, 3), offsets);
+    assert!(pack(&[0, 0, 0], 0).is_empty());
+}
+// Tests:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBitPackingTests-m2j6y4d08tj0][Tests:2]]
+fn encode_with_bit_packing<T>(data: impl Iterator<Item = T>) -> (Footer, Vec<u8>)
+where
+    T: ColumnItem,
+{
+    let options = EncodeOptions {
+        use_bit_packing: true,
+        ..EncodeOptions::plain()
+    };
+    let mut encoded_data = Vec::new();
+    let footer = encode_column_with_options(data, &mut encoded_data, options).unwrap();
+    (footer, encoded_data)
+}
+
+#[test]
+fn test_encode_decode_small_range_with_bit_packing() {
+    let data: Vec<u64> = (0..10_000u64).map(|i| 1_000_000 + i * 37 % 101).collect();
+    let (footer, encoded_data) = encode_with_bit_packing(data.iter().copied());
+    let page = footer.columns[0].pages[0];
+    assert_eq!(
+        page.encoding,
+        PageEncoding::BitPacked {
+            min: 1_000_000,
+            bit_width: 7
+        }
+    );
+    let mut plain_encoded_data = Vec::new();
+    encode_column_with_options(
+        data.iter().copied(),
+        &mut plain_encoded_data,
+        EncodeOptions::plain(),
+    )
+    .unwrap();
+    assert!(encoded_data.len() * 3 < plain_encoded_data.len());
+    assert_equal(
+        decode_column::<u64>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.into_iter().map(rle::Values::single),
+    );
+}
+
+#[test]
+fn test_encode_decode_constant_and_extreme_values_with_bit_packing() {
+    let data = std::iter::repeat_n(-7i32, 50_000).chain([i32::MIN, i32::MAX, 0]);
+    let (footer, encoded_data) = encode_with_bit_packing(data.clone());
+    assert!(footer.columns[0].pages.len() > 1);
+    assert_eq!(
+        footer.columns[0].pages[0].encoding,
+        PageEncoding::BitPacked {
+            min: -7,
+            bit_width: 0
+        }
+    );
+    assert_equal(
+        decode_column::<i32>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.map(rle::Values::single),
+    );
+}
+// Tests:2 ends here
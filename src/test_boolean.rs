@@ -0,0 +1,81 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:21]]
+use crate::boolean::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:21 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBooleanEncodingTests-e5q9w3k15tj0][Tests:1]]
+#[test]
+fn test_encode_decode_mixes_runs_and_literals() {
+    let values: Vec<bool> = (0..100)
+        .map(|i| (20..60).contains(&i) || i % 3 == 2)
+        .collect();
+    let encoded = encode(&values).unwrap();
+    assert_eq!(
+        encoded.len(),
+        [
+            1, // varint literal header
+            3, // 20 literal values
+            1, // varint run header
+            1, // varint literal header
+            5, // 40 literal values
+        ]
+        .iter()
+        .sum()
+    );
+    let decoded = decode(&mut encoded.as_slice(), values.len()).unwrap();
+    assert_eq!(
+        decoded[20],
+        rle::Values {
+            value: true,
+            run_length: 40
+        }
+    );
+    assert_equal(decoded.iter().flat_map(|v| v.repeated().copied()), values);
+}
+
+#[test]
+fn test_decode_more_values_than_page_fails() {
+    let encoded = encode(&[true; 100]).unwrap();
+    assert!(matches!(
+        decode(&mut encoded.as_slice(), 50),
+        Err(Error::PageCountMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_boolean_column_packs_8_values_per_byte() {
+    let data: Vec<bool> = (0..100_000u32).map(|i| i.count_ones() % 2 == 0).collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.iter().copied(), &mut encoded_data).unwrap();
+    // Pages are cut by rows, so packing does not lead to more pages.
+    assert_eq!(footer.columns[0].pages.len(), 13);
+    for page in footer.columns[0].pages.iter() {
+        assert_eq!(page.encoding, PageEncoding::PackedBooleans);
+        // A single literal with a 3 byte varint header.
+        assert_eq!(page.compressed_size, page.values_count.div_ceil(8) + 3);
+    }
+    assert_equal(
+        decode_column::<bool>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        data,
+    );
+}
+
+#[test]
+fn test_nullable_booleans() {
+    let data = [Some(true), None, None, Some(false), Some(true)];
+    let mut encoded_data = Vec::new();
+    encode_column(data.into_iter(), &mut encoded_data).unwrap();
+    assert_equal(
+        decode_column::<Option<bool>>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        data,
+    );
+}
+// Tests:1 ends here
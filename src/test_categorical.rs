@@ -0,0 +1,104 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:28]]
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:28 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCategoricalColumnsTests-l6x2h9v22tj0][Tests:1]]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Level {
+    Debug,
+    Info,
+    Warn,
+}
+
+column_enum!(Level { Debug, Info, Warn });
+
+fn levels() -> Vec<Level> {
+    (0..10_000)
+        .map(|idx| match idx % 10 {
+            0 => Level::Warn,
+            1..=3 => Level::Debug,
+            _ => Level::Info,
+        })
+        .collect()
+}
+
+#[test]
+fn test_enum_round_trip() {
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(levels().into_iter(), &mut encoded_data).unwrap();
+    let column = &footer.columns[0];
+    assert_eq!(column.data_type, DataType::Categorical);
+    assert_eq!(column.categories, ["Warn", "Debug", "Info"]);
+    assert_eq!(column.dictionary, None);
+    assert!(column
+        .pages
+        .iter()
+        .all(|p| p.encoding != PageEncoding::Plain));
+    assert_equal(
+        decode_column::<Level>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        levels(),
+    );
+}
+
+#[test]
+fn test_decode_codes_and_labels() {
+    let mut encoded_data = Vec::new();
+    encode_column(levels().into_iter(), &mut encoded_data).unwrap();
+    let mut reader = decode_column::<Category>(Cursor::new(&encoded_data)).unwrap();
+    assert_eq!(
+        reader.get(2).unwrap(),
+        Category {
+            code: 1,
+            label: "Debug".to_string()
+        }
+    );
+    assert_eq!(reader.get(9).unwrap().code, 2);
+    assert!(matches!(
+        decode_column::<String>(Cursor::new(&encoded_data)),
+        Err(Error::DataTypeMismatch {
+            expected: DataType::Categorical,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_labels_round_trip() {
+    let data: Vec<Option<Category>> = (0..1_000)
+        .map(|idx| match idx % 4 {
+            0 => None,
+            _ => Some(Category::new(format!("region-{}", idx % 3))),
+        })
+        .collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(
+        footer.columns[0].categories,
+        ["region-1", "region-2", "region-0"]
+    );
+    assert_equal(
+        decode_column::<Option<Category>>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>())
+            .map(|c| c.map(|c| c.label)),
+        data.into_iter().map(|c| c.map(|c| c.label)),
+    );
+}
+
+#[test]
+fn test_unknown_category() {
+    let mut encoded_data = Vec::new();
+    encode_column([Category::new("Fatal")].into_iter(), &mut encoded_data).unwrap();
+    let mut reader = decode_column::<Level>(Cursor::new(&encoded_data)).unwrap();
+    assert!(matches!(
+        reader.next(),
+        Some(Err(Error::UnknownCategory { label, .. })) if label == "Fatal"
+    ));
+}
+// Tests:1 ends here
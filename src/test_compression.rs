@@ -0,0 +1,87 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:14]]
+use crate::compression::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:14 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCompressionTests-q8n4x2j10tj0][Tests:1]]
+const ALL_COMPRESSION: [Compression; 5] = [
+    Compression::None,
+    Compression::Zstd,
+    Compression::Lz4,
+    Compression::Snappy,
+    Compression::Deflate,
+];
+
+#[test]
+fn test_compress_decompress_round_trip() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i % 13) as u8).collect();
+    for compression in ALL_COMPRESSION {
+        let compressed = compress(compression, &data).unwrap();
+        if compression != Compression::None {
+            assert!(compressed.len() < data.len(), "{:?}", compression);
+        }
+        assert_eq!(
+            decompress(compression, &compressed, data.len()).unwrap(),
+            data
+        );
+        assert!(decompress(compression, &compressed, data.len() + 1).is_err());
+    }
+}
+// Tests:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCompressionTests-q8n4x2j10tj0][Tests:2]]
+#[test]
+fn test_encode_decode_with_compression() {
+    let names: Vec<String> = (0..100).map(|i| format!("customer-{}", i * 7919)).collect();
+    let data = (0..20_000).map(|i| names[i * 31 % 100].clone());
+    let mut uncompressed_data = Vec::new();
+    encode_column_with_options(data.clone(), &mut uncompressed_data, EncodeOptions::plain())
+        .unwrap();
+    for compression in ALL_COMPRESSION {
+        let options = EncodeOptions {
+            compression,
+            ..EncodeOptions::plain()
+        };
+        let mut encoded_data = Vec::new();
+        let footer = encode_column_with_options(data.clone(), &mut encoded_data, options).unwrap();
+        for page in footer.columns[0].pages.iter() {
+            assert_eq!(page.compression, compression);
+            if compression != Compression::None {
+                assert!(page.compressed_size < page.uncompressed_size);
+            }
+        }
+        if compression != Compression::None {
+            assert!(encoded_data.len() * 2 < uncompressed_data.len());
+        }
+        assert_equal(
+            decode_column::<String>(Cursor::new(encoded_data))
+                .unwrap()
+                .map(Result::unwrap),
+            data.clone().map(rle::Values::single),
+        );
+    }
+}
+
+#[test]
+fn test_incompressible_page_is_stored_uncompressed() {
+    let options = EncodeOptions {
+        compression: Compression::Zstd,
+        ..EncodeOptions::plain()
+    };
+    let mut encoded_data = Vec::new();
+    let footer =
+        encode_column_with_options([1u8, 2, 3].into_iter(), &mut encoded_data, options).unwrap();
+    let page = footer.columns[0].pages[0];
+    assert_eq!(page.compression, Compression::None);
+    assert_eq!(page.compressed_size, 3);
+    assert_eq!(page.uncompressed_size, 3);
+    assert_equal(
+        decode_column::<u8>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        [1u8, 2, 3].map(rle::Values::single),
+    );
+}
+// Tests:2 ends here
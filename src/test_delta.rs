@@ -0,0 +1,158 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:12]]
+use crate::delta::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:12 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDeltaEncodingTests-w3q8z5b08tj0][Tests:1]]
+#[test]
+fn test_zigzag_round_trip() {
+    assert_eq!(zigzag_encode(0), 0);
+    assert_eq!(zigzag_encode(-1), 1);
+    assert_eq!(zigzag_encode(1), 2);
+    assert_eq!(zigzag_encode(-2), 3);
+    for v in [
+        0,
+        1,
+        -1,
+        i64::MAX as i128,
+        i64::MIN as i128,
+        u64::MAX as i128,
+    ] {
+        assert_eq!(zigzag_decode(zigzag_encode(v)), v);
+    }
+}
+
+#[test]
+fn test_delta_encoder_and_decoder_round_trip() {
+    let data = [1000i128, 1010, 1020, 1031, 1041, -5];
+    let mut encoder = DeltaEncoder::new(false);
+    let encoded: Vec<u128> = data.iter().map(|v| encoder.encode(*v)).collect();
+    assert_eq!(
+        encoded[1..5]
+            .iter()
+            .map(|v| zigzag_decode(*v))
+            .collect::<Vec<_>>(),
+        [10, 10, 11, 10]
+    );
+    let mut decoder = DeltaDecoder::new(false);
+    assert_equal(encoded.into_iter().map(|v| decoder.decode(v)), data);
+
+    let mut encoder = DeltaEncoder::new(true);
+    let encoded: Vec<u128> = data.iter().map(|v| encoder.encode(*v)).collect();
+    assert_eq!(
+        encoded[2..5]
+            .iter()
+            .map(|v| zigzag_decode(*v))
+            .collect::<Vec<_>>(),
+        [0, 1, -1]
+    );
+    let mut decoder = DeltaDecoder::new(true);
+    assert_equal(encoded.into_iter().map(|v| decoder.decode(v)), data);
+}
+// Tests:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDeltaEncodingTests-w3q8z5b08tj0][Tests:2]]
+fn encode_with_delta<T>(data: impl Iterator<Item = T>) -> (Footer, Vec<u8>)
+where
+    T: ColumnItem,
+{
+    let options = EncodeOptions {
+        use_rle: true,
+        use_delta: true,
+        ..EncodeOptions::plain()
+    };
+    let mut encoded_data = Vec::new();
+    let footer = encode_column_with_options(data, &mut encoded_data, options).unwrap();
+    (footer, encoded_data)
+}
+
+#[test]
+fn test_encode_decode_sorted_ids_with_delta() {
+    let data = 1_000_000_000u64..1_000_100_000u64;
+    let (footer, encoded_data) = encode_with_delta(data.clone());
+    let pages = &footer.columns[0].pages;
+    assert!(pages.len() > 1);
+    for page in pages {
+        assert_eq!(page.encoding, PageEncoding::Delta);
+        // The first value followed by a single run of 1s.
+        assert_eq!(page.encoded_values_count, 2);
+    }
+    assert_equal(
+        decode_column::<u64>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        data,
+    );
+}
+
+#[test]
+fn test_encode_decode_timestamps_with_delta_of_delta() {
+    let data: Vec<i64> = (0..100_000i64)
+        .map(|i| 1_680_000_000_000 + i * 1000 + i % 3 - 1)
+        .collect();
+    let (footer, encoded_data) = encode_with_delta(data.iter().copied());
+    assert!(footer.columns[0]
+        .pages
+        .iter()
+        .all(|p| p.encoding == PageEncoding::DeltaOfDelta));
+    let mut plain_encoded_data = Vec::new();
+    encode_column_with_options(
+        data.iter().copied(),
+        &mut plain_encoded_data,
+        EncodeOptions::plain(),
+    )
+    .unwrap();
+    assert!(encoded_data.len() * 4 < plain_encoded_data.len());
+    assert_equal(
+        decode_column::<i64>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.into_iter().map(rle::Values::single),
+    );
+}
+
+#[test]
+fn test_encode_decode_u128_ids_with_delta() {
+    let data = (u128::MAX - 10_000)..=u128::MAX;
+    let (footer, encoded_data) = encode_with_delta(data.clone());
+    assert!(footer.columns[0]
+        .pages
+        .iter()
+        .all(|p| p.encoding == PageEncoding::Delta));
+    assert_equal(
+        decode_column::<u128>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        data,
+    );
+}
+
+#[test]
+fn test_encode_decode_negative_deltas_and_repeats() {
+    let data = [5i16, 5, 5, -3, i16::MIN, i16::MAX, 0, 0];
+    let (_, encoded_data) = encode_with_delta(data.into_iter());
+    assert_equal(
+        decode_column::<i16>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        data,
+    );
+}
+
+#[test]
+fn test_delta_encoding_is_ignored_for_strings() {
+    let (footer, encoded_data) = encode_with_delta(["a", "b"].into_iter());
+    assert_eq!(footer.columns[0].pages[0].encoding, PageEncoding::Plain);
+    assert_equal(
+        decode_column::<String>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        ["a", "b"].map(|s| rle::Values::single(s.to_string())),
+    );
+}
+// Tests:2 ends here
@@ -0,0 +1,89 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:13]]
+use crate::dictionary::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:13 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDictionaryEncodingTests-c9v2m1f07tj0][Tests:1]]
+#[test]
+fn test_dictionary_assigns_codes_in_order_of_appearance() {
+    let mut dictionary = DictionaryBuilder::new(1024);
+    assert_eq!(dictionary.code_for(&"nyc").unwrap(), Some(0));
+    assert_eq!(dictionary.code_for(&"sf").unwrap(), Some(1));
+    assert_eq!(dictionary.code_for(&"nyc").unwrap(), Some(0));
+    assert_eq!(dictionary.code_for(&"la").unwrap(), Some(2));
+    assert_eq!(dictionary.len(), 3);
+    // Strings take up string_length + 1.
+    assert_eq!(dictionary.encoded_values().len(), 4 + 3 + 3);
+}
+
+#[test]
+fn test_dictionary_that_is_full_only_returns_existing_codes() {
+    let mut dictionary = DictionaryBuilder::new(8);
+    assert_eq!(dictionary.code_for(&"nyc").unwrap(), Some(0));
+    assert_eq!(dictionary.code_for(&"sf").unwrap(), Some(1));
+    assert!(!dictionary.is_full());
+    assert_eq!(dictionary.code_for(&"la").unwrap(), None);
+    assert!(dictionary.is_full());
+    assert_eq!(dictionary.code_for(&"sf").unwrap(), Some(1));
+}
+// Tests:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDictionaryEncodingTests-c9v2m1f07tj0][Tests:2]]
+#[test]
+fn test_encode_decode_with_dictionary_and_rle() {
+    let data =
+        std::iter::repeat_n("new york", 100).chain(["san francisco", "los angeles", "new york"]);
+    let options = EncodeOptions {
+        use_rle: true,
+        use_dictionary: true,
+        ..EncodeOptions::plain()
+    };
+    let mut encoded_data = Vec::new();
+    let footer = encode_column_with_options(data, &mut encoded_data, options).unwrap();
+    let page = footer.columns[0].pages[0];
+    assert_eq!(page.encoding, PageEncoding::Dictionary);
+    assert!(page.use_rle);
+    assert_equal(
+        decode_column::<String>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        [
+            rle::Values {
+                value: "new york".to_string(),
+                run_length: 100,
+            },
+            rle::Values::single("san francisco".to_string()),
+            rle::Values::single("los angeles".to_string()),
+            rle::Values::single("new york".to_string()),
+        ],
+    );
+}
+
+#[test]
+fn test_full_dictionary_falls_back_to_plain_encoding() {
+    let names: Vec<String> = (0..10)
+        .map(|i| format!("a rather long customer name {i}"))
+        .collect();
+    // The first 5 names fill up the dictionary.
+    let data = (0..20_000).map(|i| names[i / 10_000 * 5 + i % 5].clone());
+    let options = EncodeOptions {
+        use_dictionary: true,
+        max_dictionary_size: 168,
+        ..EncodeOptions::plain()
+    };
+    let mut encoded_data = Vec::new();
+    let footer = encode_column_with_options(data.clone(), &mut encoded_data, options).unwrap();
+    let column = &footer.columns[0];
+    assert_eq!(column.dictionary.map(|d| d.values_count), Some(5));
+    assert_eq!(column.pages[0].encoding, PageEncoding::Dictionary);
+    assert_eq!(column.pages.last().unwrap().encoding, PageEncoding::Plain);
+    assert_equal(
+        decode_column::<String>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.map(rle::Values::single),
+    );
+}
+// Tests:2 ends here
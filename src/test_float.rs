@@ -0,0 +1,104 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:18]]
+use crate::float::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:18 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingFloatingPointEncodingTests-k9d3f7m14tj0][Tests:1]]
+#[test]
+fn test_total_eq_compares_bits() {
+    assert!(total_eq(&f64::NAN, &f64::NAN));
+    assert!(!total_eq(&0.0f64, &-0.0f64));
+    assert!(total_eq(&1.5f32, &1.5f32));
+    assert!(total_eq(&"a".to_string(), &"a".to_string()));
+}
+
+#[test]
+fn test_split_and_join_streams() {
+    let values = [0x0403_0201u64, 0x0807_0605];
+    let streams = split_streams(&values, 4);
+    assert_eq!(streams, [1, 5, 2, 6, 3, 7, 4, 8]);
+    assert_eq!(join_streams(&streams, 4, 2), values);
+}
+
+#[test]
+fn test_rle_combines_nan_values() {
+    let data = [f64::NAN, f64::NAN, 0.0, -0.0, -0.0];
+    let options = EncodeOptions {
+        use_xor: false,
+        ..EncodeOptions::default()
+    };
+    let mut encoded_data = Vec::new();
+    encode_column_with_options(data.into_iter(), &mut encoded_data, options).unwrap();
+    let values: Vec<_> = decode_column::<f64>(Cursor::new(encoded_data))
+        .unwrap()
+        .map(Result::unwrap)
+        .map(|v| (v.value.to_bits(), v.run_length))
+        .collect();
+    assert_eq!(
+        values,
+        [
+            (f64::NAN.to_bits(), 2),
+            (0.0f64.to_bits(), 1),
+            ((-0.0f64).to_bits(), 2)
+        ]
+    );
+}
+
+#[test]
+fn test_byte_stream_split_is_used_with_compression() {
+    let data: Vec<f64> = (0..10_000)
+        .map(|i| 20.0 + (i as f64 / 100.0).sin())
+        .collect();
+    let options = EncodeOptions {
+        compression: Compression::Zstd,
+        use_xor: false,
+        ..EncodeOptions::default()
+    };
+    let mut encoded_data = Vec::new();
+    let footer =
+        encode_column_with_options(data.iter().copied(), &mut encoded_data, options).unwrap();
+    assert!(footer.columns[0]
+        .pages
+        .iter()
+        .all(|p| p.encoding == PageEncoding::ByteStreamSplit));
+    let mut plain_data = Vec::new();
+    let plain_options = EncodeOptions {
+        compression: Compression::Zstd,
+        ..EncodeOptions::plain()
+    };
+    encode_column_with_options(data.iter().copied(), &mut plain_data, plain_options).unwrap();
+    assert!(encoded_data.len() < plain_data.len());
+    assert_equal(
+        decode_column::<f64>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.into_iter().map(rle::Values::single),
+    );
+}
+
+#[test]
+fn test_nullable_floats() {
+    let data = [Some(1.5f32), None, Some(f32::NAN), Some(f32::NAN)];
+    let options = EncodeOptions {
+        use_xor: false,
+        ..EncodeOptions::default()
+    };
+    let mut encoded_data = Vec::new();
+    encode_column_with_options(data.into_iter(), &mut encoded_data, options).unwrap();
+    let values: Vec<_> = decode_column::<Option<f32>>(Cursor::new(encoded_data))
+        .unwrap()
+        .map(Result::unwrap)
+        .map(|v| (v.value.map(f32::to_bits), v.run_length))
+        .collect();
+    assert_eq!(
+        values,
+        [
+            (Some(1.5f32.to_bits()), 1),
+            (None, 1),
+            (Some(f32::NAN.to_bits()), 2)
+        ]
+    );
+}
+// Tests:1 ends here
@@ -1,17 +1,19 @@
-// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:8]]
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:9]]
 use super::*;
 use itertools::assert_equal;
 use std::io::Cursor;
-// Dependencies:8 ends here
+// Dependencies:9 ends here
 
 // [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:1]]
 fn test_can_encode_and_decode_for_type<T>(values: [T; 2])
 where
-    T: 'static + Clone + Encode + Decode + Eq + std::fmt::Debug,
+    T: 'static + ColumnItem + Clone + PartialEq + std::fmt::Debug,
+    T::Value: FromColumnValue,
 {
     let data: Vec<T> = values.to_vec();
     let mut encoded_data = Vec::new();
-    encode_column(data.into_iter(), &mut encoded_data, false).unwrap();
+    encode_column_with_options(data.into_iter(), &mut encoded_data, EncodeOptions::plain())
+        .unwrap();
     assert_equal(
         decode_column::<T>(Cursor::new(encoded_data))
             .unwrap()
@@ -41,6 +43,14 @@ fn test_encode_decode_several() {
     test_can_encode_and_decode_for_type::<u32>([1, 2]);
     test_can_encode_and_decode_for_type::<i64>([-1, 1]);
     test_can_encode_and_decode_for_type::<u64>([1, 2]);
+    test_can_encode_and_decode_for_type::<i128>([i128::MIN, i128::MAX]);
+    test_can_encode_and_decode_for_type::<u128>([u128::MAX, 0]);
+    test_can_encode_and_decode_for_type::<isize>([isize::MIN, 1]);
+    test_can_encode_and_decode_for_type::<usize>([usize::MAX, 2]);
+    test_can_encode_and_decode_for_type::<f32>([-1.5, 2.25]);
+    test_can_encode_and_decode_for_type::<f64>([f64::MIN, 2.5]);
+    test_can_encode_and_decode_for_type::<bool>([true, false]);
+    test_can_encode_and_decode_for_type::<Vec<u8>>([vec![1, 2], vec![]]);
     test_can_encode_and_decode_for_type::<String>(["a".to_string(), "b".to_string()]);
 }
 // Tests:2 ends here
@@ -50,18 +60,38 @@ fn test_encode_decode_several() {
 fn test_encode_decode_integer() {
     let data: Vec<i64> = vec![-1, 10, 10, 10, 11, 12, 12, 10];
     let mut encoded_data = Vec::new();
-    encode_column(data.into_iter(), &mut encoded_data, false).unwrap();
+    encode_column_with_options(data.into_iter(), &mut encoded_data, EncodeOptions::plain())
+        .unwrap();
     assert_eq!(
         encoded_data.len(),
         [
+            4, // magic bytes
             8, // data contains 8 values of varint with size 1.
+            1, // varint footer:version
+            1, // varint footer:columns_count
+            1, // string footer:column1:name
             1, // u8 footer:data_type
-            1, // u8 footer:use_rle
+            2, // option footer:column1:integer_width
+            1, // option footer:column1:logical_type
+            1, // u8 footer:column1:nullable
+            1, // u8 footer:column1:repeated
+            1, // varint footer:column1:null_count
+            1, // option footer:column1:dictionary
+            1, // varint footer:column1:categories
             1, // varint footer:pages_count
             1, // varint footer:page1:file_offset
             1, // varint footer:page1:values_count
+            1, // varint footer:page1:null_count
             1, // varint footer:page1:encoded_values_count
+            1, // u8 footer:page1:encoding
+            1, // u8 footer:page1:use_rle
+            1, // u8 footer:page1:compression
+            1, // varint footer:page1:uncompressed_size
+            1, // varint footer:page1:compressed_size
+            5, // varint footer:page1:checksum
+            4, // u32 footer_checksum
             8, // u64 footer_size
+            4, // magic bytes
         ]
         .iter()
         .sum()
@@ -115,18 +145,38 @@ fn test_encode_decode_integer() {
 fn test_encode_decode_string() {
     let data: Vec<&'static str> = vec!["foo", "foo", "foo", "bar", "baz", "foo"];
     let mut encoded_data = Vec::new();
-    encode_column(data.into_iter(), &mut encoded_data, false).unwrap();
+    encode_column_with_options(data.into_iter(), &mut encoded_data, EncodeOptions::plain())
+        .unwrap();
     assert_eq!(
         encoded_data.len(),
         [
+            4,  // magic bytes
             24, // data contains 6 values of varint with size 4.
+            1,  // varint footer:version
+            1,  // varint footer:columns_count
+            1,  // string footer:column1:name
             1,  // u8 footer:data_type
-            1,  // u8 footer:use_rle
+            1,  // option footer:column1:integer_width
+            1,  // option footer:column1:logical_type
+            1,  // u8 footer:column1:nullable
+            1,  // u8 footer:column1:repeated
+            1,  // varint footer:column1:null_count
+            1,  // option footer:column1:dictionary
+            1,  // varint footer:column1:categories
             1,  // varint footer:pages_count
             1,  // varint footer:page1:file_offset
             1,  // varint footer:page1:values_count
+            1,  // varint footer:page1:null_count
             1,  // varint footer:page1:encoded_values_count
+            1,  // u8 footer:page1:encoding
+            1,  // u8 footer:page1:use_rle
+            1,  // u8 footer:page1:compression
+            1,  // varint footer:page1:uncompressed_size
+            1,  // varint footer:page1:compressed_size
+            5,  // varint footer:page1:checksum
+            4,  // u32 footer_checksum
             8,  // u64 footer_size
+            4,  // magic bytes
         ]
         .iter()
         .sum()
@@ -172,10 +222,15 @@ fn test_encode_decode_string() {
 fn test_encode_decode_string_with_rle() {
     let data = ["foo", "foo", "foo", "bar", "baz", "foo"];
     let mut encoded_data = Vec::new();
-    let footer = encode_column(data.into_iter(), &mut encoded_data, true).unwrap();
+    let options = EncodeOptions {
+        use_rle: true,
+        ..EncodeOptions::plain()
+    };
+    let footer = encode_column_with_options(data.into_iter(), &mut encoded_data, options).unwrap();
     assert_eq!(
         encoded_data.len(),
         [
+            4, // magic bytes
             4, // page1:element1:rle_element string "foo" of encoding size 4.
             1, // page1:element1:rle_run_length varint of size 1.
             4, // page1:element2:rle_element string "bar" of encoding size 4.
@@ -184,13 +239,31 @@ fn test_encode_decode_string_with_rle() {
             1, // page1:element3:rle_run_length varint of size 1.
             4, // page1:element3:rle_element string "foo" of encoding size 4.
             1, // page1:element3:rle_run_length varint of size 1.
+            1, // varint footer:version
+            1, // varint footer:columns_count
+            1, // string footer:column1:name
             1, // u8 footer:data_type
-            1, // u8 footer:use_rle
+            1, // option footer:column1:integer_width
+            1, // option footer:column1:logical_type
+            1, // u8 footer:column1:nullable
+            1, // u8 footer:column1:repeated
+            1, // varint footer:column1:null_count
+            1, // option footer:column1:dictionary
+            1, // varint footer:column1:categories
             1, // varint footer:pages_count
             1, // varint footer:page1:file_offset
             1, // varint footer:page1:values_count
+            1, // varint footer:page1:null_count
             1, // varint footer:page1:encoded_values_count
+            1, // u8 footer:page1:encoding
+            1, // u8 footer:page1:use_rle
+            1, // u8 footer:page1:compression
+            1, // varint footer:page1:uncompressed_size
+            1, // varint footer:page1:compressed_size
+            5, // varint footer:page1:checksum
+            4, // u32 footer_checksum
             8, // u64 footer_size
+            4, // magic bytes
         ]
         .iter()
         .sum(),
@@ -228,16 +301,17 @@ fn test_encode_decode_string_with_rle() {
 // [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:6]]
 #[test]
 fn encode_on_many_values_outputs_several_pages() {
-    let values = std::iter::repeat(-1i64).take(1_000_000);
+    let values = std::iter::repeat_n(-1i64, 1_000_000);
     let mut encoded_data = Vec::new();
-    let footer = encode_column(values, &mut encoded_data, false).unwrap();
-    assert!(footer.pages.len() > 1, "{:?}", footer);
+    let footer =
+        encode_column_with_options(values, &mut encoded_data, EncodeOptions::plain()).unwrap();
+    assert!(footer.columns[0].pages.len() > 1, "{:?}", footer);
     assert_eq!(decode_footer(Cursor::new(&encoded_data)).unwrap(), footer);
     assert_equal(
         decode_column::<i64>(Cursor::new(&encoded_data))
             .unwrap()
             .map(Result::unwrap),
-        std::iter::repeat(rle::Values::single(-1i64)).take(1_000_000),
+        std::iter::repeat_n(rle::Values::single(-1i64), 1_000_000),
     );
 }
 // Tests:6 ends here
@@ -248,7 +322,7 @@ fn decode_on_wrong_data_type_fails() {
     // SignedInteger.
     let values = std::iter::once(-1i64);
     let mut encoded_data = Vec::new();
-    encode_column(values, &mut encoded_data, false).unwrap();
+    encode_column(values, &mut encoded_data).unwrap();
 
     assert!(decode_column::<u64>(Cursor::new(&encoded_data)).is_err());
     assert!(decode_column::<String>(Cursor::new(&encoded_data)).is_err());
@@ -256,3 +330,796 @@ fn decode_on_wrong_data_type_fails() {
     assert!(decode_column::<u8>(Cursor::new(&encoded_data)).is_err());
 }
 // Tests:7 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:8]]
+#[test]
+fn test_encode_decode_table() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table.write_column("id", [1u64, 2, 3].into_iter()).unwrap();
+    table
+        .write_column("city", ["nyc", "nyc", "sf"].into_iter())
+        .unwrap();
+    let footer = table.finish().unwrap();
+    assert_equal(
+        footer.columns.iter().map(|c| c.name.as_str()),
+        ["id", "city"],
+    );
+    assert_equal(
+        footer.columns.iter().map(|c| c.data_type),
+        [DataType::UnsignedInteger, DataType::String],
+    );
+
+    let mut reader = TableReader::new(Cursor::new(encoded_data)).unwrap();
+    assert_eq!(reader.footer(), &footer);
+    assert_equal(
+        reader.column::<String>("city").unwrap().map(Result::unwrap),
+        [
+            rle::Values {
+                value: "nyc".to_string(),
+                run_length: 2,
+            },
+            rle::Values::single("sf".to_string()),
+        ],
+    );
+    assert_equal(
+        reader.column::<u64>("id").unwrap().map(Result::unwrap),
+        [1u64, 2, 3].map(rle::Values::single),
+    );
+    assert!(reader.column::<u64>("missing").is_err());
+    assert!(reader.column::<u64>("city").is_err());
+}
+// Tests:8 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:9]]
+#[test]
+fn test_table_with_many_pages_per_column() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_column("a", (0..100_000i64).map(|x| x % 7))
+        .unwrap();
+    table
+        .write_column("b", (0..100_000u32).map(|x| x / 3))
+        .unwrap();
+    let footer = table.finish().unwrap();
+    assert!(
+        footer.columns.iter().all(|c| c.pages.len() > 1),
+        "{:?}",
+        footer
+    );
+
+    // The first column is decoded by default.
+    assert_equal(
+        decode_column::<i64>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        (0..100_000i64).map(|x| x % 7),
+    );
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert_equal(
+        reader
+            .column::<u32>("b")
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        (0..100_000u32).map(|x| x / 3),
+    );
+}
+// Tests:9 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:10]]
+#[test]
+fn test_table_with_duplicate_column_fails() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table.write_column("a", [1u8].into_iter()).unwrap();
+    assert!(table.write_column("a", [2u8].into_iter()).is_err());
+}
+// Tests:10 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:11]]
+#[test]
+fn test_encoding_is_selected_per_column() {
+    let cities = ["new york", "san francisco", "los angeles"];
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table.write_column("id", 1_000_000u64..1_010_000).unwrap();
+    table
+        .write_column("small", (0..10_000u64).map(|x| 1_000_000 + x * 37 % 101))
+        .unwrap();
+    table
+        .write_column("city", (0..10_000).map(|i| cities[i % 7 % 3]))
+        .unwrap();
+    table
+        .write_column("name", (0..10_000).map(|i| format!("name-{i}")))
+        .unwrap();
+    let footer = table.finish().unwrap();
+    let first_page = |name: &str| {
+        let column = footer.columns.iter().find(|c| c.name == name).unwrap();
+        (column.pages[0].encoding, column.pages[0].use_rle)
+    };
+    assert_eq!(first_page("id"), (PageEncoding::Delta, true));
+    assert_eq!(
+        first_page("small"),
+        (
+            PageEncoding::BitPacked {
+                min: 1_000_000,
+                bit_width: 7
+            },
+            false
+        )
+    );
+    assert_eq!(first_page("city"), (PageEncoding::Dictionary, false));
+    assert_eq!(first_page("name"), (PageEncoding::Plain, false));
+    let city_column = footer.columns.iter().find(|c| c.name == "city").unwrap();
+    assert_eq!(city_column.dictionary.map(|d| d.values_count), Some(3));
+    let name_column = footer.columns.iter().find(|c| c.name == "name").unwrap();
+    assert_eq!(name_column.dictionary, None);
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert_equal(
+        reader
+            .column::<u64>("id")
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        1_000_000u64..1_010_000,
+    );
+    assert_equal(
+        reader
+            .column::<String>("city")
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        (0..10_000).map(|i| cities[i % 7 % 3].to_string()),
+    );
+    assert_equal(
+        reader.column::<String>("name").unwrap().map(Result::unwrap),
+        (0..10_000).map(|i| rle::Values::single(format!("name-{i}"))),
+    );
+}
+// Tests:11 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:12]]
+#[test]
+fn test_seek_to_row_and_skip_rows() {
+    let data = (0..100_000u64).map(|x| x / 10);
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data, &mut encoded_data).unwrap();
+    assert!(footer.columns[0].pages.len() > 1, "{:?}", footer);
+    let mut reader = decode_column::<u64>(Cursor::new(&encoded_data)).unwrap();
+
+    // Partway through a run in a later page.
+    reader.seek_to_row(54_321).unwrap();
+    assert_eq!(
+        reader.next().unwrap().unwrap(),
+        rle::Values {
+            value: 5432,
+            run_length: 9
+        }
+    );
+    reader.skip_rows(1_005).unwrap();
+    assert_equal(
+        reader
+            .take(2)
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        std::iter::repeat_n(5533, 5).chain(std::iter::repeat_n(5534, 10)),
+    );
+
+    // Seeking backwards and to the end.
+    let mut reader = decode_column::<u64>(Cursor::new(&encoded_data)).unwrap();
+    reader.seek_to_row(99_999).unwrap();
+    reader.seek_to_row(3).unwrap();
+    assert_eq!(
+        reader.next().unwrap().unwrap(),
+        rle::Values {
+            value: 0,
+            run_length: 7
+        }
+    );
+    reader.seek_to_row(100_000).unwrap();
+    assert!(reader.next().is_none());
+    reader.skip_rows(10).unwrap();
+    assert!(reader.next().is_none());
+}
+// Tests:12 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:13]]
+#[test]
+fn test_get_and_take_rows() {
+    let cities = ["new york", "san francisco", "los angeles"];
+    let data = (0..100_000).map(|i| format!("{}-{}", cities[i % 3], i / 1000));
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone(), &mut encoded_data).unwrap();
+    assert!(footer.columns[0].pages.len() > 1, "{:?}", footer);
+    let data: Vec<String> = data.collect();
+    let mut reader = decode_column::<String>(Cursor::new(&encoded_data)).unwrap();
+    assert_eq!(reader.rows_count(), 100_000);
+
+    assert_eq!(reader.get(77_777).unwrap(), data[77_777]);
+    assert_eq!(reader.get(0).unwrap(), data[0]);
+    assert!(matches!(
+        reader.get(100_000),
+        Err(Error::RowOutOfRange {
+            row: 100_000,
+            rows_count: 100_000
+        })
+    ));
+
+    let rows = [99_999, 5, 60_000, 5, 0, 60_001, 12_345];
+    assert_equal(
+        reader.take_rows(&rows).unwrap(),
+        rows.iter().map(|row| data[*row].clone()),
+    );
+    // The reader continues after the last row that was fetched.
+    assert_eq!(reader.next().unwrap().unwrap().value, data[99_999]);
+    assert!(reader.next().is_none());
+
+    assert!(reader.take_rows(&[1, 100_000]).is_err());
+    assert!(reader.take_rows(&[]).unwrap().is_empty());
+}
+// Tests:13 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:14]]
+#[test]
+fn test_decode_range() {
+    let data = (0..100_000u64).map(|x| x / 10);
+    let mut encoded_data = Vec::new();
+    encode_column(data.clone(), &mut encoded_data).unwrap();
+    let decode_range_values = |range: std::ops::Range<usize>| {
+        decode_range::<u64>(Cursor::new(&encoded_data), range)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+    };
+
+    let values = decode_range_values(12_345..56_789);
+    assert_eq!(
+        values.first(),
+        Some(&rle::Values {
+            value: 1234,
+            run_length: 5
+        })
+    );
+    assert_eq!(
+        values.last(),
+        Some(&rle::Values {
+            value: 5678,
+            run_length: 9
+        })
+    );
+    assert_equal(
+        values
+            .iter()
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        data.clone().skip(12_345).take(56_789 - 12_345),
+    );
+
+    assert_eq!(
+        decode_range_values(3..4),
+        [rle::Values {
+            value: 0,
+            run_length: 1
+        }]
+    );
+    assert!(decode_range_values(10..10).is_empty());
+    assert_eq!(
+        decode_range_values(99_999..100_000),
+        [rle::Values {
+            value: 9999,
+            run_length: 1
+        }]
+    );
+    assert!(decode_range_values(100_000..100_000).is_empty());
+
+    let decode_range_err = |range: std::ops::Range<usize>| {
+        decode_range::<u64>(Cursor::new(&encoded_data), range).err()
+    };
+    assert!(matches!(
+        decode_range_err(99_999..200_000),
+        Some(Error::RowOutOfRange {
+            row: 199_999,
+            rows_count: 100_000
+        })
+    ));
+    assert!(matches!(
+        decode_range_err(200_000..300_000),
+        Some(Error::RowOutOfRange {
+            row: 299_999,
+            rows_count: 100_000
+        })
+    ));
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = 5..3;
+    assert!(matches!(
+        decode_range_err(reversed),
+        Some(Error::RowOutOfRange {
+            row: 5,
+            rows_count: 100_000
+        })
+    ));
+}
+// Tests:14 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:15]]
+#[test]
+fn test_integer_width_is_enforced() {
+    let mut encoded_data = Vec::new();
+    let footer = encode_column([1u64, 70_000].into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(footer.columns[0].integer_width, Some(8));
+    assert!(matches!(
+        decode_column::<u16>(Cursor::new(&encoded_data)),
+        Err(Error::IntegerWidthMismatch { width: 8, .. })
+    ));
+    assert!(matches!(
+        decode_column::<u128>(Cursor::new(&encoded_data)),
+        Err(Error::IntegerWidthMismatch { width: 8, .. })
+    ));
+    let options = DecodeOptions {
+        allow_widening: true,
+    };
+    assert!(matches!(
+        decode_column_with_options::<u32>(Cursor::new(&encoded_data), options),
+        Err(Error::IntegerWidthMismatch { width: 8, .. })
+    ));
+    assert!(matches!(
+        decode_column_with_options::<i128>(Cursor::new(&encoded_data), options),
+        Err(Error::IntegerWidthMismatch { width: 8, .. })
+    ));
+    assert_equal(
+        decode_column_with_options::<u128>(Cursor::new(&encoded_data), options)
+            .unwrap()
+            .map(Result::unwrap),
+        [1u128, 70_000].map(rle::Values::single),
+    );
+}
+
+#[test]
+fn test_widening_every_encoding() {
+    let data: Vec<u8> = (0..5000).map(|i| (i / 7 % 250) as u8).collect();
+    let options = DecodeOptions {
+        allow_widening: true,
+    };
+    for encode_options in [EncodeOptions::default(), EncodeOptions::plain()] {
+        let mut encoded_data = Vec::new();
+        let data = data.iter().copied().map(Some);
+        encode_column_with_options(data.clone(), &mut encoded_data, encode_options).unwrap();
+        assert_equal(
+            decode_column_with_options::<Option<u64>>(Cursor::new(&encoded_data), options)
+                .unwrap()
+                .map(Result::unwrap)
+                .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+            data.map(|v| v.map(u64::from)),
+        );
+    }
+}
+
+#[test]
+fn test_errors_can_be_matched() {
+    fn assert_send_sync<T: Send + Sync + std::error::Error>() {}
+    assert_send_sync::<Error>();
+
+    let mut encoded_data = Vec::new();
+    encode_column_with_options(
+        [1u64, 2, 3].into_iter(),
+        &mut encoded_data,
+        EncodeOptions::plain(),
+    )
+    .unwrap();
+    assert!(matches!(
+        decode_column::<String>(Cursor::new(&encoded_data)),
+        Err(Error::DataTypeMismatch {
+            expected: DataType::UnsignedInteger,
+            ..
+        })
+    ));
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert!(matches!(
+        reader.column::<u64>("missing"),
+        Err(Error::ColumnNotFound { .. })
+    ));
+
+    assert!(matches!(
+        decode_footer(Cursor::new(&[1u8, 2, 3])),
+        Err(Error::CorruptFooter { .. })
+    ));
+    let mut bad_footer_size = encoded_data.clone();
+    let footer_size_start = bad_footer_size.len() - 12;
+    bad_footer_size[footer_size_start..footer_size_start + 8]
+        .copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(matches!(
+        decode_footer(Cursor::new(&bad_footer_size)),
+        Err(Error::CorruptFooter { .. })
+    ));
+
+    // Claim the page has one more value than it does.
+    let mut footer = decode_footer(Cursor::new(&encoded_data)).unwrap();
+    footer.columns[0].pages[0].values_count += 1;
+    let mut bad_page = encoded_data[..7].to_vec();
+    encode::encode_footer_impl(&mut bad_page, &footer).unwrap();
+    assert!(matches!(
+        decode_column::<u64>(Cursor::new(&bad_page))
+            .unwrap()
+            .next()
+            .unwrap(),
+        Err(Error::PageCountMismatch {
+            expected: 4,
+            actual: 3
+        })
+    ));
+}
+// Tests:15 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:16]]
+#[test]
+fn test_magic_bytes_and_version() {
+    let mut encoded_data = Vec::new();
+    let footer = encode_column([1u64, 2, 3].into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(footer.version, FORMAT_VERSION);
+    assert_eq!(encoded_data[..4], MAGIC_BYTES);
+    assert_eq!(encoded_data[encoded_data.len() - 4..], MAGIC_BYTES);
+
+    let mut bad_start = encoded_data.clone();
+    bad_start[0] = b'X';
+    assert!(matches!(
+        decode_footer(Cursor::new(&bad_start)),
+        Err(Error::InvalidMagicBytes { found }) if found == *b"XCOL"
+    ));
+    let mut bad_end = encoded_data.clone();
+    bad_end.push(0);
+    assert!(matches!(
+        decode_column::<u64>(Cursor::new(&bad_end)),
+        Err(Error::InvalidMagicBytes { .. })
+    ));
+
+    // A file positioned after a prefix is read relative to its first byte.
+    let mut prefixed = b"prefix".to_vec();
+    prefixed.extend_from_slice(&encoded_data);
+    let mut r = Cursor::new(&prefixed);
+    r.set_position(6);
+    assert_eq!(decode_footer(&mut r).unwrap(), footer);
+
+    // Version 0 only exists in the layout without magic bytes.
+    let older_footer = Footer {
+        version: 0,
+        ..footer.clone()
+    };
+    let mut older = MAGIC_BYTES.to_vec();
+    encode::encode_footer_impl(&mut older, &older_footer).unwrap();
+    assert!(matches!(
+        decode_footer(Cursor::new(&older)),
+        Err(Error::UnsupportedVersion { version: 0 })
+    ));
+    let newer_footer = Footer {
+        version: FORMAT_VERSION + 1,
+        ..footer
+    };
+    let mut newer = MAGIC_BYTES.to_vec();
+    encode::encode_footer_impl(&mut newer, &newer_footer).unwrap();
+    assert!(matches!(
+        decode_footer(Cursor::new(&newer)),
+        Err(Error::UnsupportedVersion { version }) if version == FORMAT_VERSION + 1
+    ));
+}
+// Tests:16 ends here
+
+// [[file:../wills-columnar-format.org::#APITests-vfh696o03tj0][Tests:17]]
+// Lays out pages of integers like the version 0 encoder without run length
+// encoding: the values of each page, the footer and the size of the footer.
+fn encode_v0_integer_pages(pages: &[&[u64]]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut page_infos = Vec::new();
+    for page in pages {
+        let file_offset = data.len() as i64;
+        for value in page.iter() {
+            bincode::encode_into_std_write(value, &mut data, BINCODE_DATA_CONFIG).unwrap();
+        }
+        page_infos.push((file_offset, page.len(), page.len()));
+    }
+    let footer = (DataType::UnsignedInteger, false, page_infos);
+    let footer_size =
+        bincode::encode_into_std_write(footer, &mut data, BINCODE_DATA_CONFIG).unwrap();
+    data.extend((footer_size as u64).to_le_bytes());
+    data
+}
+
+#[test]
+fn test_decode_v0_files() {
+    // Written by the version 0 encoder with
+    // encode_column([1u64, 1, 1, 300, 7].into_iter(), &mut w, true).
+    let integers = [
+        1, 3, 251, 44, 1, 1, 7, 1, // page 1
+        2, 1, 1, 0, 5, 3, // footer
+        6, 0, 0, 0, 0, 0, 0, 0, // footer size
+    ];
+    let footer = decode_footer(Cursor::new(&integers)).unwrap();
+    assert_eq!(footer.version, 0);
+    assert_eq!(footer.columns.len(), 1);
+    let column = &footer.columns[0];
+    assert_eq!(column.name, "");
+    assert_eq!(column.data_type, DataType::UnsignedInteger);
+    assert_eq!(column.integer_width, None);
+    assert_eq!(column.pages[0].compressed_size, 8);
+    assert_equal(
+        decode_column::<u64>(Cursor::new(&integers))
+            .unwrap()
+            .map(Result::unwrap),
+        [
+            rle::Values {
+                value: 1,
+                run_length: 3,
+            },
+            rle::Values::single(300),
+            rle::Values::single(7),
+        ],
+    );
+    // Version 0 does not record integer widths.
+    let mut reader = decode_column::<u16>(Cursor::new(&integers)).unwrap();
+    assert_eq!(reader.get(3).unwrap(), 300);
+    verify_file(Cursor::new(&integers)).unwrap();
+
+    // Written by the version 0 encoder with
+    // encode_column(["nyc", "sf", "nyc"].into_iter(), &mut w, false).
+    let strings = [
+        3, 110, 121, 99, 2, 115, 102, 3, 110, 121, 99, // page 1
+        4, 0, 1, 0, 3, 3, // footer
+        6, 0, 0, 0, 0, 0, 0, 0, // footer size
+    ];
+    let mut table = TableReader::new(Cursor::new(&strings)).unwrap();
+    assert_equal(table.read_rows::<String>("").unwrap(), ["nyc", "sf", "nyc"]);
+
+    // Every value takes 9 bytes.
+    let values: Vec<u64> = (1..=2_000u64).map(|v| v << 40).collect();
+    let pages: Vec<&[u64]> = values.chunks(228).collect();
+    let several_pages = encode_v0_integer_pages(&pages);
+    let footer = decode_footer(Cursor::new(&several_pages)).unwrap();
+    assert_eq!(footer.columns[0].pages.len(), 9);
+    assert_eq!(footer.columns[0].pages[1].file_offset, 228 * 9);
+    let mut reader = decode_column::<u64>(Cursor::new(&several_pages)).unwrap();
+    assert_eq!(reader.get(1_999).unwrap(), values[1_999]);
+    assert_eq!(
+        reader.take_rows(&[0, 228, 1_000]).unwrap(),
+        [values[0], values[228], values[1_000]]
+    );
+    verify_file(Cursor::new(&several_pages)).unwrap();
+
+    // Neither a version 1 nor a version 0 file.
+    let mut truncated = several_pages.clone();
+    truncated.truncate(several_pages.len() - 1);
+    assert!(matches!(
+        decode_footer(Cursor::new(&truncated)),
+        Err(Error::InvalidMagicBytes { .. })
+    ));
+}
+// Tests:17 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationChecksumsTests-g7c1m4t24tj0][Tests:1]]
+#[test]
+fn test_checksums() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_column("a", (0..50_000u32).map(|x| x % 9))
+        .unwrap();
+    table
+        .write_column("b", (0..50_000).map(|x| format!("value-{}", x % 11)))
+        .unwrap();
+    let footer = table.finish().unwrap();
+    verify_file(Cursor::new(&encoded_data)).unwrap();
+
+    // Flip a bit in the last page of the first column.
+    let page = *footer.columns[0].pages.last().unwrap();
+    let mut bad_page = encoded_data.clone();
+    bad_page[page.file_offset as usize] ^= 1;
+    assert!(matches!(
+        verify_file(Cursor::new(&bad_page)),
+        Err(Error::ChecksumMismatch { file_offset, .. }) if file_offset == page.file_offset
+    ));
+    let mut reader = TableReader::new(Cursor::new(&bad_page)).unwrap();
+    assert!(reader
+        .column::<u32>("a")
+        .unwrap()
+        .any(|v| matches!(v, Err(Error::ChecksumMismatch { .. }))));
+    assert_equal(
+        reader.read_rows::<String>("b").unwrap(),
+        (0..50_000).map(|x| format!("value-{}", x % 11)),
+    );
+
+    // Turn "value-0" into "walue-0" within the dictionary of the second column.
+    let dictionary = footer.columns[1].dictionary.unwrap();
+    let mut bad_dictionary = encoded_data.clone();
+    bad_dictionary[dictionary.file_offset as usize + 1] ^= 1;
+    assert!(matches!(
+        verify_file(Cursor::new(&bad_dictionary)),
+        Err(Error::ChecksumMismatch { file_offset, .. }) if file_offset == dictionary.file_offset
+    ));
+    let mut reader = TableReader::new(Cursor::new(&bad_dictionary)).unwrap();
+    assert!(matches!(
+        reader.column::<String>("b"),
+        Err(Error::ChecksumMismatch { .. })
+    ));
+
+    // Flip a bit in the footer.
+    let mut bad_footer = encoded_data.clone();
+    let footer_end = bad_footer.len() - 16;
+    bad_footer[footer_end - 1] ^= 1;
+    assert!(matches!(
+        decode_footer(Cursor::new(&bad_footer)),
+        Err(Error::ChecksumMismatch { .. })
+    ));
+    assert!(verify_file(Cursor::new(&bad_footer)).is_err());
+}
+// Tests:1 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFooterValidationTests-h3n6d1q25tj0][Tests:1]]
+// Replaces the footer of a file, keeping the data before it.
+fn with_footer_bytes(encoded_data: &[u8], footer_bytes: &[u8]) -> Vec<u8> {
+    let footer_size_start = encoded_data.len() - 12;
+    let mut footer_size = [0u8; 8];
+    footer_size.copy_from_slice(&encoded_data[footer_size_start..footer_size_start + 8]);
+    let footer_start = footer_size_start - 4 - u64::from_le_bytes(footer_size) as usize;
+    let mut file = encoded_data[..footer_start].to_vec();
+    file.extend_from_slice(footer_bytes);
+    file.extend_from_slice(&crc32c::crc32c(footer_bytes).to_le_bytes());
+    file.extend_from_slice(&(footer_bytes.len() as u64).to_le_bytes());
+    file.extend_from_slice(&MAGIC_BYTES);
+    file
+}
+
+#[test]
+fn test_footer_validation() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_column("a", (0..10_000u32).map(|x| x % 7))
+        .unwrap();
+    table
+        .write_column("b", (0..10_000).map(|x| format!("value-{}", x % 13)))
+        .unwrap();
+    let footer = table.finish().unwrap();
+    assert!(footer.columns[0].pages.len() > 1);
+
+    let decode_changed = |change: &dyn Fn(&mut Footer)| {
+        let mut footer = footer.clone();
+        change(&mut footer);
+        let footer_bytes = bincode::encode_to_vec(&footer, BINCODE_DATA_CONFIG).unwrap();
+        decode_footer(Cursor::new(with_footer_bytes(&encoded_data, &footer_bytes)))
+    };
+    assert_eq!(decode_changed(&|_| {}).unwrap(), footer);
+    let changes: [&dyn Fn(&mut Footer); 12] = [
+        &|f| f.columns[1].name = "a".to_string(),
+        &|f| f.columns.swap(0, 1),
+        &|f| f.columns[0].pages[0].file_offset = -1,
+        &|f| f.columns[0].pages[1].file_offset += 1,
+        &|f| f.columns[0].pages[0].compressed_size = usize::MAX,
+        &|f| f.columns[0].pages[0].values_count = usize::MAX,
+        &|f| f.columns[0].pages[0].null_count = f.columns[0].pages[0].values_count + 1,
+        &|f| f.columns[0].pages[0].uncompressed_size += 1,
+        &|f| {
+            f.columns[0].pages[0].encoding = PageEncoding::BitPacked {
+                min: 0,
+                bit_width: 200,
+            }
+        },
+        &|f| f.columns[0].null_count = 1,
+        &|f| f.columns[1].dictionary.as_mut().unwrap().file_offset = 0,
+        &|f| f.columns[1].dictionary.as_mut().unwrap().values_count = usize::MAX,
+    ];
+    for change in changes {
+        assert!(matches!(
+            decode_changed(change),
+            Err(Error::CorruptFooter { .. })
+        ));
+    }
+
+    assert!(matches!(
+        encode_column(
+            [List(vec![0u8; MAX_PAGE_VALUES + 1])].into_iter(),
+            &mut Vec::new()
+        ),
+        Err(Error::PageTooLarge { values_count }) if values_count == MAX_PAGE_VALUES + 1
+    ));
+}
+// Tests:1 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFooterValidationTests-h3n6d1q25tj0][Tests:2]]
+#[test]
+fn test_page_encoding_must_match_data_type() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_column("binary", (0..100u8).map(|x| vec![x; 3]))
+        .unwrap();
+    table.write_column("int", 0..100u32).unwrap();
+    let footer = table.finish().unwrap();
+
+    let encodings = [
+        (0, PageEncoding::ByteStreamSplit),
+        (0, PageEncoding::Xor),
+        (0, PageEncoding::PackedBooleans),
+        (0, PageEncoding::Delta),
+        (1, PageEncoding::FixedSizeBinary { size: 4 }),
+    ];
+    for (column, encoding) in encodings {
+        let mut footer = footer.clone();
+        footer.columns[column].pages[0].encoding = encoding;
+        let footer_bytes = bincode::encode_to_vec(&footer, BINCODE_DATA_CONFIG).unwrap();
+        let file = with_footer_bytes(&encoded_data, &footer_bytes);
+        verify_file(Cursor::new(&file)).unwrap_err();
+        assert!(matches!(
+            TableReader::new(Cursor::new(&file)).map(|_| ()),
+            Err(Error::CorruptFooter { .. })
+        ));
+    }
+}
+// Tests:2 ends here
+
+// [[file:../wills-columnar-format.org::#FormatSpecificationFooterValidationTests-h3n6d1q25tj0][Tests:3]]
+fn decode_all<T>(reader: &mut TableReader<Cursor<Vec<u8>>>, name: &str)
+where
+    T: 'static + ColumnItem + Clone,
+    T::Value: FromColumnValue,
+{
+    if let Ok(column) = reader.column::<T>(name) {
+        column.for_each(drop);
+    }
+}
+
+#[test]
+fn test_changed_footers_do_not_panic() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table.write_column("a", 0..3_000i64).unwrap();
+    table
+        .write_column(
+            "b",
+            (0..3_000).map(|x| (x % 5 != 0).then(|| format!("value-{}", x % 7))),
+        )
+        .unwrap();
+    table
+        .write_column("c", (0..1_000).map(|x| List(vec![x as f64; x % 4])))
+        .unwrap();
+    table
+        .write_column("d", (0..3_000).map(|x| x % 3 == 0))
+        .unwrap();
+    let footer = table.finish().unwrap();
+    let footer_bytes = bincode::encode_to_vec(&footer, BINCODE_DATA_CONFIG).unwrap();
+
+    // xorshift64 keeps the test deterministic.
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    for _ in 0..2_000 {
+        let mut changed = footer_bytes.clone();
+        for _ in 0..1 + next() % 4 {
+            let idx = (next() % changed.len() as u64) as usize;
+            changed[idx] = next() as u8;
+        }
+        let file = with_footer_bytes(&encoded_data, &changed);
+        let mut reader = match TableReader::new(Cursor::new(file)) {
+            Ok(reader) => reader,
+            Err(_) => continue,
+        };
+        let names: Vec<String> = reader
+            .footer()
+            .columns
+            .iter()
+            .map(|c| c.name.clone())
+            .collect();
+        for name in names.iter() {
+            decode_all::<i64>(&mut reader, name);
+            decode_all::<Option<String>>(&mut reader, name);
+            decode_all::<List<f64>>(&mut reader, name);
+            decode_all::<bool>(&mut reader, name);
+            decode_all::<Vec<u8>>(&mut reader, name);
+        }
+    }
+}
+// Tests:3 ends here
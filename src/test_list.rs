@@ -0,0 +1,81 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:34]]
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:34 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingListColumnsTests-e5n1v7k19tj0][Tests:1]]
+fn tags(row: usize) -> List<String> {
+    let tags = ["red", "green", "blue"];
+    List((0..row % 4).map(|idx| tags[idx % 3].to_string()).collect())
+}
+
+#[test]
+fn test_list_round_trip() {
+    let data: Vec<List<String>> = (0..20_000).map(tags).collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    let column = &footer.columns[0];
+    assert!(column.repeated);
+    assert_eq!(column.data_type, DataType::String);
+    assert!(column.pages.len() > 1);
+    assert!(column
+        .pages
+        .iter()
+        .all(|p| p.encoding == PageEncoding::Dictionary));
+    assert_eq!(
+        column.pages.iter().map(|p| p.values_count).sum::<usize>(),
+        data.len()
+    );
+    assert_equal(
+        decode_column::<List<String>>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        data.clone(),
+    );
+    let mut reader = decode_column::<List<String>>(Cursor::new(&encoded_data)).unwrap();
+    assert_eq!(reader.get(4_003).unwrap(), data[4_003]);
+}
+
+#[test]
+fn test_nullable_list_round_trip() {
+    let data: Vec<Option<List<i64>>> = (0..1_000i64)
+        .map(|row| match row % 3 {
+            0 => None,
+            1 => Some(List(vec![])),
+            _ => Some(List((row..row + 5).collect())),
+        })
+        .collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    assert!(footer.columns[0].nullable);
+    assert_eq!(footer.columns[0].null_count, 334);
+    assert_equal(
+        decode_column::<Option<List<i64>>>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        data,
+    );
+}
+
+#[test]
+fn test_list_columns_must_be_decoded_as_lists() {
+    let mut encoded_data = Vec::new();
+    encode_column([List(vec![1u32, 2])].into_iter(), &mut encoded_data).unwrap();
+    assert!(matches!(
+        decode_column::<u32>(Cursor::new(&encoded_data)),
+        Err(Error::RepeatedMismatch { repeated: true, .. })
+    ));
+    let mut encoded_data = Vec::new();
+    encode_column([1u32, 2].into_iter(), &mut encoded_data).unwrap();
+    assert!(matches!(
+        decode_column::<List<u32>>(Cursor::new(&encoded_data)),
+        Err(Error::RepeatedMismatch {
+            repeated: false,
+            ..
+        })
+    ));
+}
+// Tests:1 ends here
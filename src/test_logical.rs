@@ -0,0 +1,121 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:23]]
+use crate::logical::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+use std::time::{Duration, UNIX_EPOCH};
+// Dependencies:23 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingLogicalTypesTests-h2c7t4v17tj0][Tests:1]]
+#[test]
+fn test_logical_type_is_stored_in_footer() {
+    let logical_type = LogicalType::Timestamp {
+        unit: TimeUnit::Milliseconds,
+        timezone: Some("America/New_York".to_string()),
+    };
+    let options = EncodeOptions {
+        logical_type: Some(logical_type.clone()),
+        ..EncodeOptions::default()
+    };
+    let data = [1_680_000_000_000i64, 1_680_000_001_000, 1_680_000_002_000];
+    let mut encoded_data = Vec::new();
+    encode_column_with_options(data.into_iter(), &mut encoded_data, options).unwrap();
+    let footer = decode_footer(Cursor::new(&encoded_data)).unwrap();
+    assert_eq!(footer.columns[0].logical_type, Some(logical_type));
+    assert_equal(
+        decode_column::<i64>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.map(rle::Values::single),
+    );
+}
+
+#[test]
+fn test_logical_type_must_match_data_type() {
+    fn encode<T: ColumnItem>(
+        data: impl Iterator<Item = T>,
+        logical_type: LogicalType,
+    ) -> Result<Footer> {
+        let options = EncodeOptions {
+            logical_type: Some(logical_type),
+            ..EncodeOptions::default()
+        };
+        encode_column_with_options(data, &mut Vec::new(), options)
+    }
+    let decimal = |precision| LogicalType::Decimal {
+        precision,
+        scale: 2,
+    };
+    assert!(encode([1i32].into_iter(), LogicalType::Date).is_ok());
+    assert!(encode([Some(1i32), None].into_iter(), LogicalType::Date).is_ok());
+    assert!(encode([1i64].into_iter(), decimal(18)).is_ok());
+    assert!(encode([1u128].into_iter(), LogicalType::Uuid).is_ok());
+    assert!(encode([vec![0u8; 16]].into_iter(), LogicalType::Uuid).is_ok());
+    assert!(matches!(
+        encode([1i64].into_iter(), LogicalType::Date),
+        Err(Error::LogicalTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        encode([1i64].into_iter(), decimal(19)),
+        Err(Error::LogicalTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        encode(["a"].into_iter(), LogicalType::Uuid),
+        Err(Error::LogicalTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        encode([vec![0u8; 16], vec![1u8]].into_iter(), LogicalType::Uuid),
+        Err(Error::InvalidLogicalValue { .. })
+    ));
+}
+
+#[test]
+fn test_system_time_conversions() {
+    let time = UNIX_EPOCH + Duration::new(1_680_000_000, 123_456_789);
+    assert_eq!(
+        timestamp_from_system_time(time, TimeUnit::Milliseconds),
+        Some(1_680_000_000_123)
+    );
+    assert_eq!(
+        system_time_from_timestamp(1_680_000_000_123_456_789, TimeUnit::Nanoseconds),
+        Some(time)
+    );
+    let before_epoch = UNIX_EPOCH - Duration::from_millis(1500);
+    assert_eq!(
+        timestamp_from_system_time(before_epoch, TimeUnit::Seconds),
+        Some(-2)
+    );
+    assert_eq!(
+        system_time_from_timestamp(-1500, TimeUnit::Milliseconds),
+        Some(before_epoch)
+    );
+    assert_eq!(
+        duration_value(Duration::from_micros(1_500), TimeUnit::Milliseconds),
+        Some(1)
+    );
+    assert_eq!(
+        duration_from_value(1_500, TimeUnit::Microseconds),
+        Some(Duration::from_micros(1_500))
+    );
+    assert_eq!(duration_from_value(-1, TimeUnit::Seconds), None);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_conversions() {
+    let time = chrono::DateTime::from_timestamp(1_680_000_000, 5_000_000).unwrap();
+    let value = timestamp_from_date_time(&time, TimeUnit::Milliseconds).unwrap();
+    assert_eq!(value, 1_680_000_000_005);
+    assert_eq!(
+        date_time_from_timestamp(value, TimeUnit::Milliseconds),
+        Some(time)
+    );
+    let date = chrono::NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+    assert_eq!(date_from_naive_date(date), -1);
+    assert_eq!(naive_date_from_date(-1), Some(date));
+    let noon = chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+    assert_eq!(time_from_naive_time(noon, TimeUnit::Seconds), 43_200);
+    assert_eq!(naive_time_from_time(43_200, TimeUnit::Seconds), Some(noon));
+    assert_eq!(naive_time_from_time(86_400, TimeUnit::Seconds), None);
+}
+// Tests:1 ends here
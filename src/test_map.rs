@@ -0,0 +1,89 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:30]]
+use crate::*;
+use itertools::assert_equal;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Cursor;
+// Dependencies:30 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingMapColumnsTests-z1m5r8g21tj0][Tests:1]]
+fn attributes(row: usize) -> BTreeMap<String, String> {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(
+        "browser".to_string(),
+        ["firefox", "chrome"][row % 2].to_string(),
+    );
+    if row.is_multiple_of(3) {
+        attributes.insert("user".to_string(), format!("user-{}", row));
+    }
+    attributes
+}
+
+#[test]
+fn test_map_round_trip() {
+    let data: Vec<Map<String, String>> = (0..5_000).map(|row| attributes(row).into()).collect();
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_struct("attributes", data.clone().into_iter())
+        .unwrap();
+    let footer = table.finish().unwrap();
+    assert_equal(
+        footer.columns.iter().map(|c| c.name.as_str()),
+        ["attributes.keys", "attributes.values"],
+    );
+    assert!(footer.columns[0]
+        .pages
+        .iter()
+        .all(|p| p.encoding == PageEncoding::Dictionary));
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    let decoded = reader
+        .read_struct::<Map<String, String>>("attributes")
+        .unwrap();
+    assert_eq!(decoded, data);
+    assert_equal(
+        decoded.into_iter().map(BTreeMap::from),
+        (0..5_000).map(attributes),
+    );
+}
+
+#[test]
+fn test_map_keys_can_be_read_alone() {
+    let data: Vec<Map<String, i64>> = (0..100i64)
+        .map(|row| HashMap::from([("a".to_string(), row)]).into())
+        .collect();
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table.write_struct("counts", data.into_iter()).unwrap();
+    table.finish().unwrap();
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert_equal(
+        reader.read_rows::<List<String>>("counts.keys").unwrap(),
+        std::iter::repeat_n(List(vec!["a".to_string()]), 100),
+    );
+}
+
+#[test]
+fn test_map_lengths_must_match() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_column("m.keys", [List(vec![1u8, 2])].into_iter())
+        .unwrap();
+    table
+        .write_column("m.values", [List(vec![true])].into_iter())
+        .unwrap();
+    table.finish().unwrap();
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert!(matches!(
+        reader.read_struct::<Map<u8, bool>>("m"),
+        Err(Error::MapLengthMismatch {
+            row: 0,
+            keys: 2,
+            values: 1
+        })
+    ));
+}
+// Tests:1 ends here
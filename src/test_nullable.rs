@@ -0,0 +1,92 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:15]]
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:15 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingNullableColumnsTests-w3h7c5r13tj0][Tests:1]]
+#[test]
+fn test_nullable_integers_round_trip() {
+    let data: Vec<Option<i64>> = (0..10_000)
+        .map(|i| if i % 3 == 0 { None } else { Some(i / 100) })
+        .collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    let column = &footer.columns[0];
+    assert!(column.nullable);
+    assert_eq!(column.null_count, 3334);
+    assert_eq!(
+        column.pages.iter().map(|p| p.null_count).sum::<usize>(),
+        3334
+    );
+    assert_eq!(
+        column.pages.iter().map(|p| p.values_count).sum::<usize>(),
+        10_000
+    );
+    assert_equal(
+        decode_column::<Option<i64>>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| std::iter::repeat_n(v.value, v.run_length as usize)),
+        data,
+    );
+}
+
+#[test]
+fn test_nullable_strings_with_long_null_runs() {
+    let data: Vec<Option<String>> = (0..20_000)
+        .map(|i| match i / 1000 % 3 {
+            0 => None,
+            _ => Some(format!("value-{}", i % 7)),
+        })
+        .collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(footer.columns[0].null_count, 7000);
+    let values: Vec<_> = decode_column::<Option<String>>(Cursor::new(encoded_data))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    // Consecutive nulls within a page are a single run.
+    assert!(values.iter().filter(|v| v.value.is_none()).count() < 100);
+    assert_equal(
+        values
+            .into_iter()
+            .flat_map(|v| std::iter::repeat_n(v.value, v.run_length as usize)),
+        data,
+    );
+}
+
+#[test]
+fn test_nullable_and_non_nullable_columns_are_interchangeable() {
+    let mut encoded_data = Vec::new();
+    let footer = encode_column([1u64, 2, 3].into_iter(), &mut encoded_data).unwrap();
+    assert!(!footer.columns[0].nullable);
+    assert_equal(
+        decode_column::<Option<u64>>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        [Some(1u64), Some(2), Some(3)].map(rle::Values::single),
+    );
+
+    let mut encoded_data = Vec::new();
+    encode_column(
+        [Some(1u64), Some(2), Some(3)].into_iter(),
+        &mut encoded_data,
+    )
+    .unwrap();
+    assert_equal(
+        decode_column::<u64>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        [1u64, 2, 3].map(rle::Values::single),
+    );
+
+    let mut encoded_data = Vec::new();
+    encode_column([Some(1u64), None].into_iter(), &mut encoded_data).unwrap();
+    assert!(matches!(
+        decode_column::<u64>(Cursor::new(encoded_data)),
+        Err(Error::UnexpectedNulls { null_count: 1, .. })
+    ));
+}
+// Tests:1 ends here
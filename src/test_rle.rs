@@ -1,9 +1,9 @@
-// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:9]]
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:10]]
 use crate::rle::*;
 use itertools::assert_equal;
-// Dependencies:9 ends here
+// Dependencies:10 ends here
 
-// [[file:../wills-columnar-format.org::#DataEncodingRunLengthEncoding-0vm696o03tj0][Run Length Encoding:5]]
+// [[file:../wills-columnar-format.org::#DataEncodingRunLengthEncoding-0vm696o03tj0][Run Length Encoding:6]]
 #[test]
 fn test_repeated_sum_equal_to_multiplication() {
     let rle_values = Values {
@@ -15,7 +15,7 @@ fn test_repeated_sum_equal_to_multiplication() {
     // More efficient.
     assert_eq!(rle_values.value * rle_values.run_length, 15);
 }
-// Run Length Encoding:5 ends here
+// Run Length Encoding:6 ends here
 
 // [[file:../wills-columnar-format.org::#DataEncodingRunLengthEncodingTests-xhn696o03tj0][Tests:1]]
 #[test]
@@ -0,0 +1,119 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:32]]
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:32 ends here
+
+// [[file:../wills-columnar-format.org::#APIStructColumnsTests-w8d3q5k20tj0][Tests:1]]
+#[derive(Clone, Debug, PartialEq)]
+struct Address {
+    city: String,
+    zip: Option<u32>,
+}
+
+column_struct!(Address { city, zip });
+
+fn addresses() -> Vec<Address> {
+    let cities = ["nyc", "sf", "austin"];
+    (0..10_000u32)
+        .map(|idx| Address {
+            city: cities[idx as usize % 3].to_string(),
+            zip: if idx % 5 == 0 {
+                None
+            } else {
+                Some(10_000 + idx)
+            },
+        })
+        .collect()
+}
+
+#[test]
+fn test_struct_round_trip() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table.write_column("id", 0..10_000u64).unwrap();
+    let children = table
+        .write_struct("address", addresses().into_iter())
+        .unwrap();
+    assert_equal(
+        children.iter().map(|c| c.name.as_str()),
+        ["address.city", "address.zip"],
+    );
+    let footer = table.finish().unwrap();
+    assert_equal(
+        footer.columns.iter().map(|c| c.name.as_str()),
+        ["id", "address.city", "address.zip"],
+    );
+    assert!(footer.columns[1]
+        .pages
+        .iter()
+        .all(|p| p.encoding == PageEncoding::Dictionary));
+    assert!(footer.columns[2].nullable);
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert_eq!(
+        reader.read_struct::<Address>("address").unwrap(),
+        addresses()
+    );
+}
+
+#[test]
+fn test_struct_fields_can_be_read_alone() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_struct("address", addresses().into_iter())
+        .unwrap();
+    table.finish().unwrap();
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert_equal(
+        reader.read_rows::<Option<u32>>("address.zip").unwrap(),
+        addresses().into_iter().map(|a| a.zip),
+    );
+    let mut zips = reader.column::<Option<u32>>("address.zip").unwrap();
+    assert_eq!(zips.get(7_001).unwrap(), Some(17_001));
+}
+
+#[test]
+fn test_struct_rows_must_match() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_column("address.city", ["nyc", "sf"].into_iter())
+        .unwrap();
+    table
+        .write_column("address.zip", [Some(1u32)].into_iter())
+        .unwrap();
+    table.finish().unwrap();
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert!(matches!(
+        reader.read_struct::<Address>("address"),
+        Err(Error::StructRowsMismatch {
+            expected: 2,
+            actual: 1,
+            ..
+        })
+    ));
+    assert!(matches!(
+        reader.read_struct::<Address>("home"),
+        Err(Error::ColumnNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_struct_rejects_logical_type() {
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    let options = EncodeOptions {
+        logical_type: Some(LogicalType::Date),
+        ..EncodeOptions::default()
+    };
+    assert!(matches!(
+        table.write_struct_with_options("address", addresses().into_iter(), options),
+        Err(Error::StructLogicalType { name }) if name == "address"
+    ));
+    assert!(table.finish().unwrap().columns.is_empty());
+}
+// Tests:1 ends here
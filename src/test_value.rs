@@ -0,0 +1,145 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:35]]
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+use std::{borrow::Cow, sync::Arc};
+// Dependencies:35 ends here
+
+// [[file:../wills-columnar-format.org::#APIColumnValuesTests-p3w8n5d18tj0][Tests:1]]
+#[derive(Clone, Debug, PartialEq)]
+struct UserId(u64);
+
+impl ColumnValue for UserId {
+    type Physical = u64;
+
+    fn to_physical(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromColumnValue for UserId {
+    fn from_physical(value: u64) -> Result<UserId> {
+        Ok(UserId(value))
+    }
+}
+
+#[test]
+fn test_newtype_column() {
+    let data = [UserId(1), UserId(2), UserId(2)];
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(footer.columns[0].data_type, DataType::UnsignedInteger);
+    assert_eq!(footer.columns[0].integer_width, Some(8));
+    assert_equal(
+        decode_column::<UserId>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        data.clone(),
+    );
+    assert_equal(
+        decode_column::<u64>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().copied().collect::<Vec<_>>()),
+        [1, 2, 2],
+    );
+
+    let data = [Some(UserId(1)), None, Some(UserId(3))];
+    let mut encoded_data = Vec::new();
+    encode_column(data.clone().into_iter(), &mut encoded_data).unwrap();
+    assert_equal(
+        decode_column::<Option<UserId>>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        data,
+    );
+}
+
+#[test]
+fn test_string_and_binary_types_are_interchangeable() {
+    let strings = ["a".to_string(), "b".to_string(), "b".to_string()];
+    let mut encoded_data = Vec::new();
+    encode_column(strings.iter().map(String::as_str), &mut encoded_data).unwrap();
+    let decoded: Vec<Arc<str>> = decode_column::<Arc<str>>(Cursor::new(&encoded_data))
+        .unwrap()
+        .map(Result::unwrap)
+        .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>())
+        .collect();
+    assert_equal(decoded.iter().map(|v| v.as_ref()), ["a", "b", "b"]);
+    assert_equal(
+        decode_column::<Box<str>>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        ["a", "b", "b"].map(Box::from),
+    );
+    assert_equal(
+        decode_column::<Cow<'static, str>>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .flat_map(|v| v.repeated().cloned().collect::<Vec<_>>()),
+        ["a", "b", "b"].map(Cow::from),
+    );
+
+    let bytes: Arc<[u8]> = Arc::from(&[1u8, 2][..]);
+    let mut encoded_data = Vec::new();
+    encode_column([bytes.clone()].into_iter(), &mut encoded_data).unwrap();
+    assert_equal(
+        decode_column::<Vec<u8>>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .map(|v| v.value),
+        [vec![1, 2]],
+    );
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Millis(i64);
+
+impl ColumnValue for Millis {
+    type Physical = i64;
+
+    fn to_physical(&self) -> i64 {
+        self.0
+    }
+
+    fn logical_type() -> Option<LogicalType> {
+        Some(LogicalType::Timestamp {
+            unit: TimeUnit::Milliseconds,
+            timezone: None,
+        })
+    }
+}
+
+impl FromColumnValue for Millis {
+    fn from_physical(value: i64) -> Result<Millis> {
+        Ok(Millis(value))
+    }
+}
+
+#[test]
+fn test_value_logical_type() {
+    let mut encoded_data = Vec::new();
+    let footer = encode_column([Millis(1), Millis(2)].into_iter(), &mut encoded_data).unwrap();
+    assert_eq!(footer.columns[0].logical_type, Millis::logical_type());
+    assert_equal(
+        decode_column::<Millis>(Cursor::new(&encoded_data))
+            .unwrap()
+            .map(Result::unwrap)
+            .map(|v| v.value),
+        [Millis(1), Millis(2)],
+    );
+
+    let mut encoded_data = Vec::new();
+    encode_column([1i64, 2].into_iter(), &mut encoded_data).unwrap();
+    assert!(matches!(
+        decode_column::<Millis>(Cursor::new(&encoded_data)),
+        Err(Error::UnexpectedLogicalType {
+            logical_type: None,
+            ..
+        })
+    ));
+}
+// Tests:1 ends here
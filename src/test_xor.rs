@@ -0,0 +1,48 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:19]]
+use crate::xor::*;
+use crate::*;
+use itertools::assert_equal;
+use std::io::Cursor;
+// Dependencies:19 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingXorEncodingTests-p2v6n8q14tj0][Tests:1]]
+#[test]
+fn test_encode_decode_round_trip() {
+    let values: Vec<u64> = [0.0, 1.0, 1.0, 1.5, -1.5, f64::NAN, f64::MAX, 1e-300]
+        .iter()
+        .map(|v: &f64| v.to_bits())
+        .collect();
+    let encoded = encode(&values, 64);
+    assert_eq!(decode(&encoded, 64, values.len()).unwrap(), values);
+    assert!(decode(&encoded[..encoded.len() - 4], 64, values.len()).is_err());
+}
+
+#[test]
+fn test_repeated_values_take_a_bit() {
+    let values = vec![1.25f32.to_bits() as u64; 80];
+    // The first value has a 2 bit header, a 12 bit window, and 9 meaningful
+    // bits. Each repeat takes 1 bit.
+    assert_eq!(
+        encode(&values, 32).len(),
+        (2 + 12 + 9 + 79usize).div_ceil(8)
+    );
+}
+
+#[test]
+fn test_xor_is_used_for_slowly_changing_values() {
+    let data: Vec<f64> = (0..10_000).map(|i| 20.0 + (i / 10) as f64 * 0.5).collect();
+    let mut encoded_data = Vec::new();
+    let footer = encode_column(data.iter().copied(), &mut encoded_data).unwrap();
+    assert!(footer.columns[0]
+        .pages
+        .iter()
+        .all(|p| p.encoding == PageEncoding::Xor));
+    assert!(encoded_data.len() < data.len() * 2);
+    assert_equal(
+        decode_column::<f64>(Cursor::new(encoded_data))
+            .unwrap()
+            .map(Result::unwrap),
+        data.into_iter().map(rle::Values::single),
+    );
+}
+// Tests:1 ends here
@@ -0,0 +1,355 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:26]]
+use std::{borrow::Cow, sync::Arc};
+
+use bincode::{Decode, Encode};
+
+use crate::{DataType, Error, LogicalType, Result};
+// Dependencies:26 ends here
+
+// [[file:../wills-columnar-format.org::#APIColumnValues-k7q2x9m18tj0][Column Values:1]]
+pub trait ColumnValue: Sized {
+    type Physical: PhysicalValue;
+
+    fn to_physical(&self) -> Self::Physical;
+
+    fn logical_type() -> Option<LogicalType> {
+        None
+    }
+}
+
+pub trait FromColumnValue: 'static + ColumnValue + Clone {
+    fn from_physical(value: Self::Physical) -> Result<Self>;
+}
+// Column Values:1 ends here
+
+// [[file:../wills-columnar-format.org::#APIColumnValues-k7q2x9m18tj0][Column Values:3]]
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub trait PhysicalValue:
+    sealed::Sealed + 'static + Clone + PartialEq + bincode::Encode + bincode::Decode
+{
+    const DATA_TYPE: DataType;
+    const INTEGER_WIDTH: Option<u8> = None;
+
+    fn to_i128(&self) -> Option<i128> {
+        None
+    }
+
+    fn from_i128(_value: i128) -> Option<Self> {
+        None
+    }
+
+    fn to_float_bits(&self) -> Option<u64> {
+        None
+    }
+
+    fn from_float_bits(_bits: u64) -> Option<Self> {
+        None
+    }
+
+    fn to_bool(&self) -> Option<bool> {
+        None
+    }
+
+    fn from_bool(_value: bool) -> Option<Self> {
+        None
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        None
+    }
+
+    fn from_bytes(_bytes: &[u8]) -> Option<Self> {
+        None
+    }
+
+    fn as_category(&self) -> Option<&str> {
+        None
+    }
+
+    fn from_category(_code: u32, _label: &str) -> Option<Self> {
+        None
+    }
+}
+
+impl sealed::Sealed for String {}
+
+impl PhysicalValue for String {
+    const DATA_TYPE: DataType = DataType::String;
+}
+// Column Values:3 ends here
+
+// [[file:../wills-columnar-format.org::#APIColumnValues-k7q2x9m18tj0][Column Values:4]]
+macro_rules! impl_column_value_for_physical {
+    ($($t:ty),*) => {
+        $(
+            impl ColumnValue for $t {
+                type Physical = $t;
+
+                fn to_physical(&self) -> $t {
+                    <$t>::clone(self)
+                }
+            }
+
+            impl FromColumnValue for $t {
+                fn from_physical(value: $t) -> Result<$t> {
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_column_value_for_physical!(
+    u8,
+    i8,
+    u16,
+    i16,
+    u32,
+    i32,
+    u64,
+    i64,
+    u128,
+    i128,
+    f32,
+    f64,
+    bool,
+    String,
+    Vec<u8>,
+    Category
+);
+
+macro_rules! impl_column_value_for_size {
+    ($($t:ty => $physical:ty),*) => {
+        $(
+            impl ColumnValue for $t {
+                type Physical = $physical;
+
+                fn to_physical(&self) -> $physical {
+                    *self as $physical
+                }
+            }
+
+            impl FromColumnValue for $t {
+                fn from_physical(value: $physical) -> Result<$t> {
+                    <$t>::try_from(value).map_err(|_| Error::IntegerOutOfRange {
+                        value: value as i128,
+                        actual: std::any::type_name::<$t>(),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_column_value_for_size!(usize => u64, isize => i64);
+
+impl ColumnValue for &str {
+    type Physical = String;
+
+    fn to_physical(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ColumnValue for &[u8] {
+    type Physical = Vec<u8>;
+
+    fn to_physical(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+macro_rules! impl_column_value_for_owned {
+    ($physical:ty, $($t:ty),*) => {
+        $(
+            impl ColumnValue for $t {
+                type Physical = $physical;
+
+                fn to_physical(&self) -> $physical {
+                    (**self).to_owned()
+                }
+            }
+
+            impl FromColumnValue for $t {
+                fn from_physical(value: $physical) -> Result<$t> {
+                    Ok(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_column_value_for_owned!(String, Box<str>, Arc<str>, Cow<'static, str>);
+impl_column_value_for_owned!(Vec<u8>, Box<[u8]>, Arc<[u8]>, Cow<'static, [u8]>);
+// Column Values:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingDeltaEncoding-h7t3n2a08tj0][Delta Encoding:4]]
+macro_rules! impl_integer_physical_value {
+    ($data_type:expr, $($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl PhysicalValue for $t {
+                const DATA_TYPE: DataType = $data_type;
+                const INTEGER_WIDTH: Option<u8> = Some(std::mem::size_of::<$t>() as u8);
+
+                fn to_i128(&self) -> Option<i128> {
+                    Some(*self as i128)
+                }
+
+                fn from_i128(value: i128) -> Option<$t> {
+                    <$t>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_physical_value!(DataType::UnsignedByte, u8);
+impl_integer_physical_value!(DataType::SignedByte, i8);
+impl_integer_physical_value!(DataType::UnsignedInteger, u16, u32, u64);
+impl_integer_physical_value!(DataType::SignedInteger, i16, i32, i64, i128);
+
+impl sealed::Sealed for u128 {}
+
+impl PhysicalValue for u128 {
+    const DATA_TYPE: DataType = DataType::UnsignedInteger;
+    const INTEGER_WIDTH: Option<u8> = Some(16);
+
+    fn to_i128(&self) -> Option<i128> {
+        Some(*self as i128)
+    }
+
+    fn from_i128(value: i128) -> Option<u128> {
+        Some(value as u128)
+    }
+}
+// Delta Encoding:4 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingFloatingPointEncoding-b6s2e9x14tj0][Floating Point Encoding:2]]
+impl sealed::Sealed for f32 {}
+
+impl PhysicalValue for f32 {
+    const DATA_TYPE: DataType = DataType::Float;
+
+    fn to_float_bits(&self) -> Option<u64> {
+        Some(f32::to_bits(*self) as u64)
+    }
+
+    fn from_float_bits(bits: u64) -> Option<f32> {
+        Some(f32::from_bits(u32::try_from(bits).ok()?))
+    }
+}
+
+impl sealed::Sealed for f64 {}
+
+impl PhysicalValue for f64 {
+    const DATA_TYPE: DataType = DataType::Double;
+
+    fn to_float_bits(&self) -> Option<u64> {
+        Some(f64::to_bits(*self))
+    }
+
+    fn from_float_bits(bits: u64) -> Option<f64> {
+        Some(f64::from_bits(bits))
+    }
+}
+// Floating Point Encoding:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBooleanEncoding-a7x4m2g15tj0][Boolean Encoding:2]]
+impl sealed::Sealed for bool {}
+
+impl PhysicalValue for bool {
+    const DATA_TYPE: DataType = DataType::Boolean;
+
+    fn to_bool(&self) -> Option<bool> {
+        Some(*self)
+    }
+
+    fn from_bool(value: bool) -> Option<bool> {
+        Some(value)
+    }
+}
+// Boolean Encoding:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingBinaryEncoding-f3z8r6d16tj0][Binary Encoding:1]]
+impl sealed::Sealed for Vec<u8> {}
+
+impl PhysicalValue for Vec<u8> {
+    const DATA_TYPE: DataType = DataType::Binary;
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Vec<u8>> {
+        Some(bytes.to_vec())
+    }
+}
+// Binary Encoding:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCategoricalColumns-s9k3d6w22tj0][Categorical Columns:1]]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct Category {
+    pub code: u32,
+    pub label: String,
+}
+
+impl Category {
+    pub fn new(label: impl Into<String>) -> Category {
+        Category {
+            code: 0,
+            label: label.into(),
+        }
+    }
+}
+
+impl sealed::Sealed for Category {}
+
+impl PhysicalValue for Category {
+    const DATA_TYPE: DataType = DataType::Categorical;
+
+    fn as_category(&self) -> Option<&str> {
+        Some(&self.label)
+    }
+
+    fn from_category(code: u32, label: &str) -> Option<Category> {
+        Some(Category {
+            code,
+            label: label.to_string(),
+        })
+    }
+}
+// Categorical Columns:1 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCategoricalColumns-s9k3d6w22tj0][Categorical Columns:2]]
+#[macro_export]
+macro_rules! column_enum {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        impl $crate::ColumnValue for $name {
+            type Physical = $crate::Category;
+
+            fn to_physical(&self) -> $crate::Category {
+                match self {
+                    $($name::$variant => $crate::Category::new(stringify!($variant)),)+
+                }
+            }
+        }
+
+        impl $crate::FromColumnValue for $name {
+            fn from_physical(value: $crate::Category) -> std::result::Result<$name, $crate::Error> {
+                match value.label.as_str() {
+                    $(stringify!($variant) => Ok($name::$variant),)+
+                    _ => Err($crate::Error::UnknownCategory {
+                        label: value.label,
+                        actual: std::any::type_name::<$name>(),
+                    }),
+                }
+            }
+        }
+    };
+}
+// Categorical Columns:2 ends here
@@ -0,0 +1,101 @@
+// [[file:../wills-columnar-format.org::#DataEncodingXorEncoding-c4j8u1t14tj0][XOR Encoding:1]]
+use std::io::{Error, ErrorKind, Result};
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits_count: usize,
+}
+
+impl BitWriter {
+    // Writes the lowest bits_count bits of value, most significant bit first.
+    fn write(&mut self, value: u64, bits_count: u32) {
+        for bit in (0..bits_count).rev() {
+            if self.bits_count.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> bit) & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 1 << (self.bits_count % 8);
+            }
+            self.bits_count += 1;
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, bits_count: u32) -> Result<u64> {
+        let mut value = 0u64;
+        for _ in 0..bits_count {
+            let byte = self
+                .bytes
+                .get(self.position / 8)
+                .ok_or(Error::from(ErrorKind::UnexpectedEof))?;
+            value = (value << 1) | ((byte >> (self.position % 8)) & 1) as u64;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+}
+
+// Encodes values that are bit_width bits wide.
+pub fn encode(values: &[u64], bit_width: u32) -> Vec<u8> {
+    let mut w = BitWriter::default();
+    let mut previous = 0u64;
+    let mut window = None;
+    for value in values.iter().copied() {
+        let xor = value ^ previous;
+        previous = value;
+        if xor == 0 {
+            w.write(0b0, 1);
+            continue;
+        }
+        let leading = xor.leading_zeros() - (u64::BITS - bit_width);
+        let trailing = xor.trailing_zeros();
+        match window {
+            Some((l, t)) if leading >= l && trailing >= t => {
+                w.write(0b10, 2);
+                w.write(xor >> t, bit_width - l - t);
+            }
+            _ => {
+                let meaningful = bit_width - leading - trailing;
+                w.write(0b11, 2);
+                w.write(leading as u64, 6);
+                w.write(meaningful as u64 - 1, 6);
+                w.write(xor >> trailing, meaningful);
+                window = Some((leading, trailing));
+            }
+        }
+    }
+    w.bytes
+}
+
+pub fn decode(encoded: &[u8], bit_width: u32, values_count: usize) -> Result<Vec<u64>> {
+    let mut r = BitReader {
+        bytes: encoded,
+        position: 0,
+    };
+    let mut previous = 0u64;
+    let (mut leading, mut trailing) = (0, 0);
+    let mut values = Vec::with_capacity(values_count);
+    for _ in 0..values_count {
+        if r.read(1)? == 1 {
+            if r.read(1)? == 1 {
+                leading = r.read(6)? as u32;
+                let meaningful = r.read(6)? as u32 + 1;
+                if leading + meaningful > bit_width {
+                    return Err(Error::new(ErrorKind::InvalidData, "invalid XOR window"));
+                }
+                trailing = bit_width - leading - meaningful;
+            }
+            previous ^= r.read(bit_width - leading - trailing)? << trailing;
+        }
+        values.push(previous);
+    }
+    Ok(values)
+}
+// XOR Encoding:1 ends here
@@ -0,0 +1,61 @@
+// [[file:../wills-columnar-format.org::#IntroductionCargotoml-cqc696o03tj0][Dependencies:33]]
+use columnar_format::{
+    column_enum, column_struct, decode_column, encode_column, TableReader, TableWriter,
+};
+use std::io::Cursor;
+// Dependencies:33 ends here
+
+// [[file:../wills-columnar-format.org::#APIStructColumnsTests-w8d3q5k20tj0][Tests:2]]
+#[derive(Clone, Debug, PartialEq)]
+struct Point {
+    x: i64,
+    label: Option<String>,
+}
+
+column_struct!(Point { x, label });
+
+#[test]
+fn test_column_struct_outside_of_crate() {
+    let points = vec![
+        Point {
+            x: 1,
+            label: Some("a".to_string()),
+        },
+        Point { x: -2, label: None },
+    ];
+    let mut encoded_data = Vec::new();
+    let mut table = TableWriter::new(&mut encoded_data);
+    table
+        .write_struct("point", points.clone().into_iter())
+        .unwrap();
+    table.finish().unwrap();
+
+    let mut reader = TableReader::new(Cursor::new(&encoded_data)).unwrap();
+    assert_eq!(reader.read_struct::<Point>("point").unwrap(), points);
+}
+// Tests:2 ends here
+
+// [[file:../wills-columnar-format.org::#DataEncodingCategoricalColumnsTests-l6x2h9v22tj0][Tests:2]]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Color {
+    Red,
+    Green,
+}
+
+column_enum!(Color { Red, Green });
+
+#[test]
+fn test_column_enum_outside_of_crate() {
+    let colors = [Color::Red, Color::Red, Color::Green];
+    let mut encoded_data = Vec::new();
+    encode_column(colors.into_iter(), &mut encoded_data).unwrap();
+    let decoded: Vec<Color> = decode_column::<Color>(Cursor::new(&encoded_data))
+        .unwrap()
+        .flat_map(|v| {
+            let v = v.unwrap();
+            v.repeated().copied().collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(decoded, colors);
+}
+// Tests:2 ends here